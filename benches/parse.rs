@@ -0,0 +1,61 @@
+//! Compares repeated `parse_bandwidth` calls with a reused `BandwidthParser`
+//! and with `validate_bandwidth_string`
+//!
+//! Run with `cargo bench --bench parse`.
+
+// benchmarks are run with a recent toolchain, not the MSRV
+#![allow(clippy::incompatible_msrv)]
+
+use human_bandwidth::{parse_bandwidth, validate_bandwidth_string, BandwidthParser};
+use std::hint::black_box;
+use std::time::Instant;
+
+const INPUTS: &[&str] = &[
+    "1Gbps",
+    "9Tbps 420Gbps",
+    "150.024kbps",
+    "32Mbps",
+    "1.5Gbps 2kbps 1bps",
+    "100 Mbps",
+];
+const ROUNDS: usize = 200_000;
+
+/// Returns the average time of one `parse` call in nanoseconds
+fn measure(mut parse: impl FnMut(&str)) -> f64 {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for s in INPUTS {
+            parse(black_box(s));
+        }
+    }
+    start.elapsed().as_secs_f64() * 1e9 / (ROUNDS * INPUTS.len()) as f64
+}
+
+fn run(name: &str, parse: impl FnMut(&str)) {
+    println!("{:<28} {:>8.1} ns/parse", name, measure(parse));
+}
+
+fn main() {
+    // warm up
+    measure(|s| {
+        black_box(parse_bandwidth(s).unwrap());
+    });
+
+    run("parse_bandwidth", |s| {
+        black_box(parse_bandwidth(s).unwrap());
+    });
+
+    run("validate_bandwidth_string", |s| {
+        black_box(validate_bandwidth_string(s)).unwrap();
+    });
+
+    let mut parser = BandwidthParser::new();
+    run("BandwidthParser::parse", |s| {
+        black_box(parser.parse(s).unwrap());
+    });
+
+    let mut parser = BandwidthParser::new();
+    run("BandwidthParser::parse_spans", |s| {
+        black_box(parser.parse_spans(s).unwrap().len());
+    });
+}
@@ -0,0 +1,141 @@
+//! Parsing many bandwidths with the same options
+
+use crate::{parse_bandwidth_with_options, Error, ParseOptions, ParsedSpan, Parser};
+use alloc::vec::Vec;
+use bandwidth::Bandwidth;
+use core::mem;
+
+/// A parser for many bandwidths with the same options, such as the lines of
+/// a config file
+///
+/// The options are set once, and the buffer of
+/// [`parse_spans`][BandwidthParser::parse_spans] is reused from one call to
+/// the next, so parsing spans allocates only while the buffer grows.
+///
+/// The parser is [`Send`], so it can be moved to a worker thread. Its
+/// methods take `&mut self` since the buffer is shared between calls:
+/// threads parsing at the same time each need their own parser.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{BandwidthParser, ParseOptions};
+///
+/// let mut parser = BandwidthParser::with_options(ParseOptions::new().allow_unlimited(true));
+/// let bws: Result<Vec<_>, _> = ["1Gbps", "500Mbps", "unlimited"]
+///     .iter()
+///     .map(|s| parser.parse(s))
+///     .collect();
+/// assert_eq!(
+///     bws,
+///     Ok(vec![Bandwidth::new(1, 0), Bandwidth::from_mbps(500), Bandwidth::MAX])
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthParser {
+    opts: ParseOptions,
+    spans: Vec<ParsedSpan>,
+}
+
+impl BandwidthParser {
+    /// Creates a parser with the default options, parsing like
+    /// [`parse_bandwidth`][crate::parse_bandwidth]
+    pub fn new() -> BandwidthParser {
+        BandwidthParser::default()
+    }
+
+    /// Creates a parser with custom options, parsing like
+    /// [`parse_bandwidth_with_options`]
+    pub fn with_options(opts: ParseOptions) -> BandwidthParser {
+        BandwidthParser {
+            opts,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Returns the options of the parser
+    pub fn options(&self) -> &ParseOptions {
+        &self.opts
+    }
+
+    /// Parses `s` like [`parse_bandwidth_with_options`]
+    pub fn parse(&mut self, s: &str) -> Result<Bandwidth, Error> {
+        parse_bandwidth_with_options(s, &self.opts)
+    }
+
+    /// Parses `s` like [`parse_bandwidth_spans`][crate::parse_bandwidth_spans],
+    /// with the options of the parser
+    ///
+    /// The spans stay valid until the next call. The keywords of
+    /// [`ParseOptions::allow_unlimited`] have no spans and fail with
+    /// [`Error::NumberExpected`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use human_bandwidth::{BandwidthParser, BandwidthUnit};
+    ///
+    /// let mut parser = BandwidthParser::new();
+    /// let spans = parser.parse_spans("1Gbps 500Mbps").unwrap();
+    /// assert_eq!(spans.len(), 2);
+    /// assert_eq!(spans[1].unit, BandwidthUnit::Mbps);
+    /// ```
+    pub fn parse_spans(&mut self, s: &str) -> Result<&[ParsedSpan], Error> {
+        self.spans.clear();
+        let mut parser = Parser::new(s, &self.opts);
+        parser.spans = Some(mem::take(&mut self.spans));
+        let res = parser.parse();
+        self.spans = parser.spans.take().unwrap_or_default();
+        res?;
+        Ok(&self.spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_bandwidth, parse_bandwidth_spans};
+
+    #[test]
+    fn test_parse() {
+        let mut parser = BandwidthParser::new();
+        for s in ["1Gbps", "9Tbps 420Gbps", "150.024kbps", "", "1Gbit"] {
+            assert_eq!(parser.parse(s), parse_bandwidth(s));
+        }
+        assert_eq!(parser.options(), &ParseOptions::default());
+
+        let opts = ParseOptions::new().allow_unlimited(true);
+        let mut parser = BandwidthParser::with_options(opts);
+        assert_eq!(parser.parse("unlimited"), Ok(Bandwidth::MAX));
+        assert_eq!(parser.options(), &opts);
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        let mut parser = BandwidthParser::new();
+        for s in [
+            "1Gbps 500Mbps",
+            "2kbps",
+            "1.5Gbps 2kbps 1bps",
+            "1Gbps 2Gbit",
+            "",
+        ] {
+            let expected = parse_bandwidth_spans(s);
+            assert_eq!(parser.parse_spans(s).map(|spans| spans.to_vec()), expected);
+        }
+        // the buffer is reused
+        let capacity = parser.spans.capacity();
+        assert!(capacity >= 3);
+        parser.parse_spans("1Gbps").unwrap();
+        assert_eq!(parser.spans.capacity(), capacity);
+        assert!(parser.parse_spans("1Gbps 2Gbit").is_err());
+        assert_eq!(parser.spans.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<BandwidthParser>();
+    }
+}
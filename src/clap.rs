@@ -0,0 +1,222 @@
+//! Value parsers for the `clap` argument parser
+
+use crate::{parse_bandwidth, parse_bandwidth_range, BandwidthRange, Error};
+use bandwidth::Bandwidth;
+use clap::builder::TypedValueParser;
+use clap::error::ErrorKind;
+use clap::{Arg, Command};
+use std::ffi::OsStr;
+
+/// Formats accepted by [`HumanBandwidthParser`], shown on errors
+const BANDWIDTH_FORMATS: &str = "a number followed by a unit, like 100Mbps, 1.5Gbps, \
+    10Gbit/s or 1Gbps 500Mbps; units are bps, kbps, Mbps, Gbps, Tbps, Pbps and Ebps, \
+    or the byte rates Bps, kBps, MBps, GBps and TBps";
+
+/// Formats accepted by [`HumanBandwidthRangeParser`], shown on errors
+const RANGE_FORMATS: &str = "two bandwidths separated by `..` or `-`, like \
+    100Mbps..1Gbps, 100Mbps-1Gbps, 100Mbps.. or ..1Gbps";
+
+/// Converts a parse error into a `clap` error naming the argument and the
+/// supported `formats`
+fn value_error(
+    cmd: &Command,
+    arg: Option<&Arg>,
+    value: &str,
+    err: Error,
+    formats: &str,
+) -> clap::Error {
+    let arg = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+    clap::Error::raw(
+        ErrorKind::ValueValidation,
+        format!(
+            "invalid value '{}' for '{}': {}\n\n  expected {}\n",
+            value, arg, err, formats
+        ),
+    )
+    .with_cmd(cmd)
+}
+
+/// Returns `value` as a `str`, or a `clap` error if it is not UTF-8
+fn to_str<'a>(cmd: &Command, value: &'a OsStr) -> Result<&'a str, clap::Error> {
+    value
+        .to_str()
+        .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))
+}
+
+/// A `clap` value parser for [`Bandwidth`] arguments, using
+/// [`parse_bandwidth`]
+///
+/// Invalid values fail with [`ErrorKind::ValueValidation`], the message
+/// gives the reason and lists the supported formats.
+///
+/// [`HumanBandwidth`][crate::HumanBandwidth] implements `FromStr` and needs
+/// no dedicated parser; this one yields a plain `Bandwidth`.
+///
+/// Only available with the `clap` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use clap::Parser;
+/// use human_bandwidth::HumanBandwidthParser;
+///
+/// #[derive(Parser)]
+/// struct Args {
+///     #[arg(long, value_parser = HumanBandwidthParser::new())]
+///     bandwidth: Bandwidth,
+/// }
+///
+/// let args = Args::parse_from(["prog", "--bandwidth", "1Gbps"]);
+/// assert_eq!(args.bandwidth, Bandwidth::from_gbps(1));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanBandwidthParser;
+
+impl HumanBandwidthParser {
+    /// Creates the parser
+    pub fn new() -> HumanBandwidthParser {
+        HumanBandwidthParser
+    }
+}
+
+impl TypedValueParser for HumanBandwidthParser {
+    type Value = Bandwidth;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Bandwidth, clap::Error> {
+        let value = to_str(cmd, value)?;
+        parse_bandwidth(value).map_err(|e| value_error(cmd, arg, value, e, BANDWIDTH_FORMATS))
+    }
+}
+
+/// A `clap` value parser for [`BandwidthRange`] arguments like
+/// `100Mbps..1Gbps`, using [`parse_bandwidth_range`]
+///
+/// Only available with the `clap` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanBandwidthRangeParser;
+
+impl HumanBandwidthRangeParser {
+    /// Creates the parser
+    pub fn new() -> HumanBandwidthRangeParser {
+        HumanBandwidthRangeParser
+    }
+}
+
+impl TypedValueParser for HumanBandwidthRangeParser {
+    type Value = BandwidthRange;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<BandwidthRange, clap::Error> {
+        let value = to_str(cmd, value)?;
+        parse_bandwidth_range(value).map_err(|e| value_error(cmd, arg, value, e, RANGE_FORMATS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("test")
+            .arg(
+                Arg::new("bandwidth")
+                    .long("bandwidth")
+                    .value_parser(HumanBandwidthParser::new()),
+            )
+            .arg(
+                Arg::new("range")
+                    .long("range")
+                    .value_parser(HumanBandwidthRangeParser::new()),
+            )
+    }
+
+    #[test]
+    fn test_parse() {
+        let matches = command()
+            .try_get_matches_from(["test", "--bandwidth", "1Gbps", "--range", "1Mbps-2Mbps"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Bandwidth>("bandwidth"),
+            Some(&Bandwidth::from_gbps(1))
+        );
+        assert_eq!(
+            matches.get_one::<BandwidthRange>("range"),
+            Some(&BandwidthRange::new(
+                Bandwidth::from_mbps(1),
+                Bandwidth::from_mbps(2)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = command()
+            .try_get_matches_from(["test", "--bandwidth", "1Gbit"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let msg = err.to_string();
+        assert!(
+            msg.contains("invalid value '1Gbit' for '--bandwidth <bandwidth>'"),
+            "{}",
+            msg
+        );
+        assert!(msg.contains("unknown bandwidth unit \"Gbit\""), "{}", msg);
+        assert!(
+            msg.contains("expected a number followed by a unit"),
+            "{}",
+            msg
+        );
+        assert!(msg.contains("byte rates Bps, kBps, MBps"), "{}", msg);
+
+        let err = command()
+            .try_get_matches_from(["test", "--range", "2Mbps-1Mbps"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("invalid bandwidth range"));
+        assert!(err.to_string().contains("100Mbps..1Gbps, 100Mbps-1Gbps"));
+    }
+
+    #[test]
+    fn test_rate_arg() {
+        let cmd = Command::new("shaper").arg(
+            Arg::new("rate")
+                .long("rate")
+                .required(true)
+                .value_parser(HumanBandwidthParser::new()),
+        );
+        let matches = cmd
+            .clone()
+            .try_get_matches_from(["shaper", "--rate", "1.5Gbps"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Bandwidth>("rate"),
+            Some(&Bandwidth::new(1, 500_000_000))
+        );
+        let matches = cmd
+            .clone()
+            .try_get_matches_from(["shaper", "--rate=100Mbit/s"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Bandwidth>("rate"),
+            Some(&Bandwidth::from_mbps(100))
+        );
+
+        let err = cmd
+            .clone()
+            .try_get_matches_from(["shaper", "--rate", "fast"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let err = cmd.try_get_matches_from(["shaper"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+}
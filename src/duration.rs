@@ -0,0 +1,172 @@
+//! Bandwidth and duration pairs, like `1Gbps*10s`
+
+use crate::{parse_bandwidth, Error};
+use bandwidth::Bandwidth;
+use std::error::Error as StdError;
+use std::fmt;
+use std::time::Duration;
+
+/// Error parsing a bandwidth and duration pair
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseBandwidthDurationError {
+    /// No `*` separates the bandwidth from the duration
+    MissingSeparator,
+    /// The bandwidth part could not be parsed
+    Bandwidth(Error),
+    /// The duration part could not be parsed
+    Duration(humantime::DurationError),
+}
+
+impl StdError for ParseBandwidthDurationError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ParseBandwidthDurationError::MissingSeparator => None,
+            ParseBandwidthDurationError::Bandwidth(e) => Some(e),
+            ParseBandwidthDurationError::Duration(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for ParseBandwidthDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBandwidthDurationError::MissingSeparator => write!(
+                f,
+                "expected bandwidth and duration separated by `*`, for example 1Gbps*10s"
+            ),
+            ParseBandwidthDurationError::Bandwidth(e) => {
+                write!(f, "failed to parse bandwidth: {}", e)
+            }
+            ParseBandwidthDurationError::Duration(e) => {
+                write!(f, "failed to parse duration: {}", e)
+            }
+        }
+    }
+}
+
+/// Parse a bandwidth and duration pair like `1Gbps*10s`
+///
+/// `1Gbps*10s` reads as a burst budget of 1Gbps for 10 seconds. The rate and
+/// the duration are returned; the burst size in bytes is given by
+/// [`bandwidth_times_duration`].
+///
+/// The bandwidth is parsed with [`parse_bandwidth`] and the duration with
+/// [`humantime::parse_duration`]. Only `*` separates them: a `/` would be
+/// ambiguous with units like `bit/s`, so `1Gbps/10s` fails with
+/// [`ParseBandwidthDurationError::MissingSeparator`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_duration_pair;
+/// use std::time::Duration;
+///
+/// assert_eq!(
+///     parse_bandwidth_duration_pair("100Mbps*5s"),
+///     Ok((Bandwidth::from_mbps(100), Duration::from_secs(5)))
+/// );
+/// assert_eq!(
+///     parse_bandwidth_duration_pair("1Gbit/s*1min"),
+///     Ok((Bandwidth::from_gbps(1), Duration::from_secs(60)))
+/// );
+/// ```
+pub fn parse_bandwidth_duration_pair(
+    s: &str,
+) -> Result<(Bandwidth, Duration), ParseBandwidthDurationError> {
+    let pos = s
+        .rfind('*')
+        .ok_or(ParseBandwidthDurationError::MissingSeparator)?;
+    let bw = parse_bandwidth(&s[..pos]).map_err(ParseBandwidthDurationError::Bandwidth)?;
+    let dur =
+        humantime::parse_duration(&s[pos + 1..]).map_err(ParseBandwidthDurationError::Duration)?;
+    Ok((bw, dur))
+}
+
+/// Returns the number of bytes transferred at `bw` during `dur`
+///
+/// Partial bytes are truncated, and the result saturates at `u64::MAX`. This
+/// is the same as [`util::transfer_size`][crate::util::transfer_size].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::bandwidth_times_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(
+///     bandwidth_times_duration(Bandwidth::from_mbps(100), Duration::from_secs(5)),
+///     62_500_000
+/// );
+/// ```
+pub fn bandwidth_times_duration(bw: Bandwidth, dur: Duration) -> u64 {
+    crate::util::transfer_size(dur, bw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(
+            parse_bandwidth_duration_pair("100Mbps*5s"),
+            Ok((Bandwidth::from_mbps(100), Duration::from_secs(5)))
+        );
+        assert_eq!(
+            parse_bandwidth_duration_pair("1Gbit/s*10s"),
+            Ok((Bandwidth::from_gbps(1), Duration::from_secs(10)))
+        );
+        assert!(matches!(
+            parse_bandwidth_duration_pair("1Gbps/10s"),
+            Err(ParseBandwidthDurationError::MissingSeparator)
+        ));
+        assert_eq!(
+            parse_bandwidth_duration_pair("1Gbps 500Mbps * 1min 30s"),
+            Ok((Bandwidth::new(1, 500_000_000), Duration::from_secs(90)))
+        );
+        assert_eq!(
+            parse_bandwidth_duration_pair("1Gbps"),
+            Err(ParseBandwidthDurationError::MissingSeparator)
+        );
+        assert_eq!(
+            parse_bandwidth_duration_pair("1Gbit*10s"),
+            Err(ParseBandwidthDurationError::Bandwidth(Error::UnknownUnit {
+                start: 1,
+                end: 5,
+                unit: "Gbit".into(),
+                value: 1,
+            }))
+        );
+        assert!(matches!(
+            parse_bandwidth_duration_pair("1Gbps*10"),
+            Err(ParseBandwidthDurationError::Duration(_))
+        ));
+    }
+
+    #[test]
+    fn test_bandwidth_times_duration() {
+        assert_eq!(
+            bandwidth_times_duration(Bandwidth::from_mbps(100), Duration::from_secs(5)),
+            62_500_000
+        );
+        assert_eq!(
+            bandwidth_times_duration(Bandwidth::from_gbps(1), Duration::from_millis(1)),
+            125_000
+        );
+        assert_eq!(
+            bandwidth_times_duration(Bandwidth::from_bps(7), Duration::from_secs(1)),
+            0
+        );
+        assert_eq!(bandwidth_times_duration(Bandwidth::ZERO, Duration::MAX), 0);
+        assert_eq!(
+            bandwidth_times_duration(Bandwidth::MAX, Duration::from_secs(1)),
+            u64::MAX
+        );
+        assert_eq!(
+            bandwidth_times_duration(Bandwidth::MAX, Duration::MAX),
+            u64::MAX
+        );
+    }
+}
@@ -0,0 +1,605 @@
+//! Extension methods for [`Bandwidth`]
+
+use crate::BandwidthUnit;
+#[cfg(feature = "alloc")]
+use crate::{format_bandwidth, FormattedBandwidth};
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use bandwidth::Bandwidth;
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for bandwidth::Bandwidth {}
+}
+
+/// Extension methods for [`Bandwidth`]
+///
+/// `Bandwidth` is defined in the [`bandwidth`] crate, so constructors and
+/// conversions provided by this crate are exposed through this trait.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::BandwidthExt;
+///
+/// let bw = Bandwidth::from_bps_u128(10_000_000_000_000_000_000).unwrap();
+/// assert_eq!(bw, Bandwidth::new(10_000_000_000, 0));
+/// ```
+pub trait BandwidthExt: sealed::Sealed + Sized {
+    /// Creates a `Bandwidth` from a raw `u128` count of bits per second
+    ///
+    /// Returns `None` if `n` exceeds [`Bandwidth::MAX`]. This is useful for
+    /// cumulative statistics where multiple bandwidths are summed in `u128`
+    /// before being stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// assert_eq!(
+    ///     Bandwidth::from_bps_u128(1_500_000_000),
+    ///     Some(Bandwidth::new(1, 500_000_000))
+    /// );
+    /// assert_eq!(Bandwidth::from_bps_u128(u128::MAX), None);
+    /// ```
+    fn from_bps_u128(n: u128) -> Option<Self>;
+
+    /// Creates a `Bandwidth` from a mantissa in bps and an SI prefix
+    ///
+    /// The prefix is one of `'k'` (or `'K'`), `'M'`, `'G'`, `'T'`, `'P'` and
+    /// `'E'`, multiplying `value` by a power of 1000. Returns `None` for any
+    /// other prefix, or if the result exceeds [`Bandwidth::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// assert_eq!(Bandwidth::from_si_prefix(1, 'G'), Some(Bandwidth::new(1, 0)));
+    /// assert_eq!(Bandwidth::from_si_prefix(1, 'X'), None);
+    /// ```
+    fn from_si_prefix(value: u64, prefix: char) -> Option<Self>;
+
+    /// Creates a `Bandwidth` from a count of bytes per second, as reported
+    /// by OS interface statistics
+    ///
+    /// This never overflows, since `u64::MAX` bytes per second is well below
+    /// [`Bandwidth::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// assert_eq!(Bandwidth::from_bytes_per_sec(125_000_000), Bandwidth::new(1, 0));
+    /// ```
+    fn from_bytes_per_sec(bytes: u64) -> Self;
+
+    /// Creates a `Bandwidth` from megabytes (`10^6` bytes) per second
+    ///
+    /// Fractions of a bit are dropped. Returns `None` if `mbytes` is negative,
+    /// not a number, or exceeds [`Bandwidth::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// assert_eq!(Bandwidth::from_megabytes_per_sec(12.5), Some(Bandwidth::from_mbps(100)));
+    /// assert_eq!(Bandwidth::from_megabytes_per_sec(-1.0), None);
+    /// ```
+    fn from_megabytes_per_sec(mbytes: f64) -> Option<Self>;
+
+    /// Returns the total number of bits per second as a `u128`
+    ///
+    /// This never overflows and is lossless, since even [`Bandwidth::MAX`]
+    /// is below `2^94`, while `gbps * 1_000_000_000` in `u64` overflows
+    /// from about 18Gbps on. It is the inverse of
+    /// [`from_bps_u128`][BandwidthExt::from_bps_u128]: the round trip gives
+    /// back every `Bandwidth` unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// let bw = Bandwidth::new(20, 5);
+    /// assert_eq!(bw.to_bps_u128(), 20_000_000_005);
+    /// assert_eq!(Bandwidth::from_bps_u128(Bandwidth::MAX.to_bps_u128()), Some(Bandwidth::MAX));
+    /// ```
+    fn to_bps_u128(self) -> u128;
+
+    /// Compares two bandwidths by their total bits per second
+    ///
+    /// Whole gigabits are compared first and the sub-gigabit parts only if
+    /// they are equal, so nothing is multiplied and nothing can overflow.
+    /// This is the same order as the `Ord` impl of `Bandwidth`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!(
+    ///     Bandwidth::new(1, 0).cmp_bps(Bandwidth::new(0, 999_999_999)),
+    ///     Ordering::Greater
+    /// );
+    /// ```
+    fn cmp_bps(self, other: Self) -> Ordering;
+
+    /// Returns the number of bits per second as an `f64`
+    ///
+    /// The exact `u128` count is converted in a single step, so the result is
+    /// the `f64` nearest to the exact value: the relative error is at most
+    /// `2^-53`, and values up to `2^53` bps are exact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// assert_eq!(Bandwidth::new(1, 1).to_f64_bps(), 1_000_000_001.0);
+    /// ```
+    fn to_f64_bps(self) -> f64;
+
+    /// Returns the number of kilobits per second as an `f64`
+    ///
+    /// This divides [`to_f64_bps`][BandwidthExt::to_f64_bps] by 1000, which
+    /// rounds once more: the result is within 1 ulp of the exact value.
+    fn to_f64_kbps(self) -> f64;
+
+    /// Returns the number of gigabits per second as an `f64`
+    ///
+    /// This divides [`to_f64_bps`][BandwidthExt::to_f64_bps] by `10^9`,
+    /// which rounds once more: the result is within 1 ulp of the exact value.
+    fn to_f64_gbps(self) -> f64;
+
+    /// Returns the number of bytes per second as an `f64`
+    ///
+    /// This divides [`to_f64_bps`][BandwidthExt::to_f64_bps] by 8, which is
+    /// exact unless the result is subnormal.
+    fn to_f64_bytes_per_sec(self) -> f64;
+
+    /// Splits the bandwidth into a whole number of `unit` and the remainder
+    ///
+    /// The first element is the bandwidth rounded down to a multiple of
+    /// `unit`, the second is what is left, so the two always add up to
+    /// `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{BandwidthExt, BandwidthUnit};
+    ///
+    /// assert_eq!(
+    ///     Bandwidth::new(1, 500_000_000).split_at_unit(BandwidthUnit::Gbps),
+    ///     (Bandwidth::new(1, 0), Bandwidth::new(0, 500_000_000))
+    /// );
+    /// ```
+    fn split_at_unit(self, unit: BandwidthUnit) -> (Self, Self);
+
+    /// Formats the bandwidth in decimal form, e.g. `1.5Gbps`
+    ///
+    /// Unlike `format_bandwidth(bw).to_string()`, this does not depend on the
+    /// `display-integer` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthExt;
+    ///
+    /// assert_eq!(Bandwidth::new(1, 0).to_human_string(), "1Gbps");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn to_human_string(self) -> String;
+
+    /// Formats the bandwidth in decimal form, e.g. `1.5Gbps`
+    #[cfg(feature = "alloc")]
+    fn to_human_decimal_string(self) -> String;
+
+    /// Formats the bandwidth in integer form, e.g. `1Gbps 500Mbps`
+    #[cfg(feature = "alloc")]
+    fn to_human_integer_string(self) -> String;
+
+    /// Creates a `Bandwidth` from a network interface speed in Mbps as
+    /// reported by Linux
+    ///
+    /// See [`linux::from_network_interface_speed`][crate::linux::from_network_interface_speed].
+    #[cfg(any(target_os = "linux", feature = "linux-compat"))]
+    fn from_network_interface_speed(speed_mbit: i64) -> Option<Self>;
+
+    /// Creates a `Bandwidth` from the speed in Mbps of an `ethtool_cmd`
+    ///
+    /// See [`linux::from_ethtool_speed`][crate::linux::from_ethtool_speed].
+    #[cfg(any(target_os = "linux", feature = "linux-compat"))]
+    fn from_ethtool_speed(speed_mbit: u32) -> Self;
+}
+
+impl BandwidthExt for Bandwidth {
+    fn from_bps_u128(n: u128) -> Option<Self> {
+        let gbps = n / 1_000_000_000;
+        if gbps > u64::MAX as u128 {
+            return None;
+        }
+        Some(Bandwidth::new(gbps as u64, (n % 1_000_000_000) as u32))
+    }
+
+    fn from_si_prefix(value: u64, prefix: char) -> Option<Self> {
+        let exp = match prefix {
+            'k' | 'K' => 1,
+            'M' => 2,
+            'G' => 3,
+            'T' => 4,
+            'P' => 5,
+            'E' => 6,
+            _ => return None,
+        };
+        Bandwidth::from_bps_u128(value as u128 * 1000u128.pow(exp))
+    }
+
+    fn from_bytes_per_sec(bytes: u64) -> Self {
+        // at most `u64::MAX * 8`, which always fits
+        Bandwidth::from_bps_u128(bytes as u128 * 8).unwrap()
+    }
+
+    fn from_megabytes_per_sec(mbytes: f64) -> Option<Self> {
+        let bits = mbytes * 8e6;
+        // also rejects NaN
+        if !(bits >= 0.0 && bits <= Bandwidth::MAX.as_bps() as f64) {
+            return None;
+        }
+        Bandwidth::from_bps_u128(bits as u128)
+    }
+
+    fn to_bps_u128(self) -> u128 {
+        self.as_bps()
+    }
+
+    fn split_at_unit(self, unit: BandwidthUnit) -> (Self, Self) {
+        let bps = self.as_bps();
+        let rem = bps % unit.scale();
+        // both parts are at most `self`, so they always fit
+        (
+            Bandwidth::from_bps_u128(bps - rem).unwrap(),
+            Bandwidth::from_bps_u128(rem).unwrap(),
+        )
+    }
+
+    fn cmp_bps(self, other: Self) -> Ordering {
+        self.as_gbps()
+            .cmp(&other.as_gbps())
+            .then_with(|| self.subgbps_bps().cmp(&other.subgbps_bps()))
+    }
+
+    fn to_f64_bps(self) -> f64 {
+        self.as_bps() as f64
+    }
+
+    fn to_f64_kbps(self) -> f64 {
+        self.to_f64_bps() / 1e3
+    }
+
+    fn to_f64_gbps(self) -> f64 {
+        self.to_f64_bps() / 1e9
+    }
+
+    fn to_f64_bytes_per_sec(self) -> f64 {
+        self.to_f64_bps() / 8.0
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn to_human_string(self) -> String {
+        self.to_human_decimal_string()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn to_human_decimal_string(self) -> String {
+        Decimal(format_bandwidth(self)).to_string()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn to_human_integer_string(self) -> String {
+        Integer(format_bandwidth(self)).to_string()
+    }
+
+    #[cfg(any(target_os = "linux", feature = "linux-compat"))]
+    fn from_network_interface_speed(speed_mbit: i64) -> Option<Self> {
+        crate::linux::from_network_interface_speed(speed_mbit)
+    }
+
+    #[cfg(any(target_os = "linux", feature = "linux-compat"))]
+    fn from_ethtool_speed(speed_mbit: u32) -> Self {
+        crate::linux::from_ethtool_speed(speed_mbit)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct Decimal(FormattedBandwidth);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_decimal(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct Integer(FormattedBandwidth);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_integer(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bps_u128() {
+        assert_eq!(Bandwidth::from_bps_u128(0), Some(Bandwidth::new(0, 0)));
+        assert_eq!(
+            Bandwidth::from_bps_u128(u64::MAX as u128),
+            Some(Bandwidth::from_bps(u64::MAX))
+        );
+        assert_eq!(
+            Bandwidth::from_bps_u128(u64::MAX as u128 + 1),
+            Some(Bandwidth::from_bps(u64::MAX) + Bandwidth::new(0, 1))
+        );
+        assert_eq!(
+            Bandwidth::from_bps_u128(Bandwidth::MAX.as_bps()),
+            Some(Bandwidth::MAX)
+        );
+        assert_eq!(Bandwidth::from_bps_u128(Bandwidth::MAX.as_bps() + 1), None);
+        assert_eq!(Bandwidth::from_bps_u128(u128::MAX), None);
+
+        assert_eq!(Bandwidth::new(0, 0).to_bps_u128(), 0);
+        assert_eq!(Bandwidth::new(1, 1).to_bps_u128(), 1_000_000_001);
+        assert_eq!(
+            Bandwidth::from_bps(u64::MAX).to_bps_u128(),
+            u64::MAX as u128
+        );
+        assert_eq!(
+            Bandwidth::MAX.to_bps_u128(),
+            u64::MAX as u128 * 1_000_000_000 + 999_999_999
+        );
+        for bw in [Bandwidth::new(42, 24), Bandwidth::MAX] {
+            assert_eq!(Bandwidth::from_bps_u128(bw.to_bps_u128()), Some(bw));
+        }
+    }
+
+    #[test]
+    fn test_from_si_prefix() {
+        assert_eq!(
+            Bandwidth::from_si_prefix(1, 'G'),
+            Some(Bandwidth::new(1, 0))
+        );
+        assert_eq!(
+            Bandwidth::from_si_prefix(5, 'k'),
+            Some(Bandwidth::from_kbps(5))
+        );
+        assert_eq!(
+            Bandwidth::from_si_prefix(5, 'K'),
+            Some(Bandwidth::from_kbps(5))
+        );
+        assert_eq!(
+            Bandwidth::from_si_prefix(32, 'M'),
+            Some(Bandwidth::from_mbps(32))
+        );
+        assert_eq!(
+            Bandwidth::from_si_prefix(2, 'T'),
+            Some(Bandwidth::new(2_000, 0))
+        );
+        assert_eq!(
+            Bandwidth::from_si_prefix(3, 'P'),
+            Some(Bandwidth::new(3_000_000, 0))
+        );
+        assert_eq!(Bandwidth::from_si_prefix(0, 'P'), Some(Bandwidth::ZERO));
+        assert_eq!(
+            Bandwidth::from_si_prefix(18_446_744_073, 'E'),
+            Some(Bandwidth::new(18_446_744_073_000_000_000, 0))
+        );
+        assert_eq!(Bandwidth::from_si_prefix(18_446_744_074, 'E'), None);
+        assert_eq!(Bandwidth::from_si_prefix(1, 'e'), None);
+        assert_eq!(
+            Bandwidth::from_si_prefix(u64::MAX, 'G'),
+            Some(Bandwidth::new(u64::MAX, 0))
+        );
+        assert_eq!(Bandwidth::from_si_prefix(u64::MAX, 'T'), None);
+        assert_eq!(Bandwidth::from_si_prefix(1, 'm'), None);
+        assert_eq!(Bandwidth::from_si_prefix(1, 'g'), None);
+        assert_eq!(Bandwidth::from_si_prefix(1, 'X'), None);
+    }
+
+    #[test]
+    fn test_cmp_bps() {
+        let cmp = |a: Bandwidth, b: Bandwidth| a.cmp_bps(b);
+        assert_eq!(
+            cmp(Bandwidth::new(1, 5), Bandwidth::new(1, 6)),
+            Ordering::Less
+        );
+        assert_eq!(
+            cmp(Bandwidth::new(1, 6), Bandwidth::new(1, 5)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            cmp(Bandwidth::new(1, 5), Bandwidth::new(1, 5)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            cmp(Bandwidth::new(2, 0), Bandwidth::new(1, 999_999_999)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            cmp(Bandwidth::new(u64::MAX, 0), Bandwidth::MAX),
+            Ordering::Less
+        );
+        assert_eq!(cmp(Bandwidth::MAX, Bandwidth::MAX), Ordering::Equal);
+        assert_eq!(
+            cmp(
+                Bandwidth::new(u64::MAX, 0),
+                Bandwidth::new(u64::MAX - 1, 999_999_999)
+            ),
+            Ordering::Greater
+        );
+        for (a, b) in [
+            (Bandwidth::ZERO, Bandwidth::new(0, 1)),
+            (Bandwidth::new(3, 7), Bandwidth::new(3, 7)),
+            (Bandwidth::MAX, Bandwidth::new(0, 999_999_999)),
+        ] {
+            assert_eq!(a.cmp_bps(b), a.cmp(&b));
+            assert_eq!(a.cmp_bps(b), a.as_bps().cmp(&b.as_bps()));
+        }
+    }
+
+    #[test]
+    fn test_to_f64() {
+        let bps = Bandwidth::new(1, 1).to_f64_bps();
+        assert!((bps - 1_000_000_001.0).abs() < (bps - 1_000_000_000.0).abs());
+        assert_eq!(bps, 1_000_000_001.0);
+        assert_eq!(Bandwidth::ZERO.to_f64_bps(), 0.0);
+        // 2^53 + 1 is not representable and rounds to even
+        assert_eq!(
+            Bandwidth::from_bps((1 << 53) + 1).to_f64_bps(),
+            (1u64 << 53) as f64
+        );
+        assert_eq!(Bandwidth::MAX.to_f64_bps(), Bandwidth::MAX.as_bps() as f64);
+
+        assert_eq!(Bandwidth::new(0, 1_500).to_f64_kbps(), 1.5);
+        assert_eq!(Bandwidth::new(1, 500_000_000).to_f64_gbps(), 1.5);
+        assert_eq!(Bandwidth::new(9420, 0).to_f64_gbps(), 9420.0);
+        assert_eq!(Bandwidth::new(0, 1).to_f64_gbps(), 1e-9);
+    }
+
+    #[test]
+    fn test_bytes_per_sec() {
+        assert_eq!(Bandwidth::from_bytes_per_sec(0), Bandwidth::ZERO);
+        assert_eq!(Bandwidth::from_bytes_per_sec(1), Bandwidth::from_bps(8));
+        assert_eq!(
+            Bandwidth::from_bytes_per_sec(125_000_000),
+            Bandwidth::new(1, 0)
+        );
+        assert_eq!(
+            Bandwidth::from_bytes_per_sec(u64::MAX).as_bps(),
+            u64::MAX as u128 * 8
+        );
+
+        assert_eq!(
+            Bandwidth::from_megabytes_per_sec(0.0),
+            Some(Bandwidth::ZERO)
+        );
+        assert_eq!(
+            Bandwidth::from_megabytes_per_sec(125.0),
+            Some(Bandwidth::new(1, 0))
+        );
+        assert_eq!(
+            Bandwidth::from_megabytes_per_sec(1e-7),
+            Some(Bandwidth::ZERO)
+        );
+        assert_eq!(
+            Bandwidth::from_megabytes_per_sec(1.25e-7),
+            Some(Bandwidth::from_bps(1))
+        );
+        assert_eq!(Bandwidth::from_megabytes_per_sec(-0.5), None);
+        assert_eq!(Bandwidth::from_megabytes_per_sec(f64::NAN), None);
+        assert_eq!(Bandwidth::from_megabytes_per_sec(f64::INFINITY), None);
+        assert_eq!(Bandwidth::from_megabytes_per_sec(1e30), None);
+        assert_eq!(
+            Bandwidth::from_megabytes_per_sec((1u64 << 60) as f64),
+            Bandwidth::from_bps_u128((1 << 63) * 1_000_000)
+        );
+
+        assert_eq!(Bandwidth::ZERO.to_f64_bytes_per_sec(), 0.0);
+        assert_eq!(Bandwidth::from_bps(4).to_f64_bytes_per_sec(), 0.5);
+        assert_eq!(Bandwidth::new(1, 0).to_f64_bytes_per_sec(), 125e6);
+        assert_eq!(
+            Bandwidth::from_bytes_per_sec(1_234_567).to_f64_bytes_per_sec(),
+            1_234_567.0
+        );
+    }
+
+    #[test]
+    fn test_split_at_unit() {
+        let bw = Bandwidth::new(1_234, 567_891_234);
+        assert_eq!(bw.split_at_unit(BandwidthUnit::Bps), (bw, Bandwidth::ZERO));
+        assert_eq!(
+            bw.split_at_unit(BandwidthUnit::Kbps),
+            (Bandwidth::new(1_234, 567_891_000), Bandwidth::new(0, 234))
+        );
+        assert_eq!(
+            bw.split_at_unit(BandwidthUnit::Mbps),
+            (
+                Bandwidth::new(1_234, 567_000_000),
+                Bandwidth::new(0, 891_234)
+            )
+        );
+        assert_eq!(
+            bw.split_at_unit(BandwidthUnit::Gbps),
+            (Bandwidth::new(1_234, 0), Bandwidth::new(0, 567_891_234))
+        );
+        assert_eq!(
+            bw.split_at_unit(BandwidthUnit::Tbps),
+            (Bandwidth::new(1_000, 0), Bandwidth::new(234, 567_891_234))
+        );
+
+        for unit in BandwidthUnit::ALL {
+            assert_eq!(
+                Bandwidth::ZERO.split_at_unit(unit),
+                (Bandwidth::ZERO, Bandwidth::ZERO)
+            );
+            let (whole, rem) = Bandwidth::MAX.split_at_unit(unit);
+            assert_eq!(whole + rem, Bandwidth::MAX);
+        }
+        assert_eq!(
+            Bandwidth::from_gbps(2_000).split_at_unit(BandwidthUnit::Tbps),
+            (Bandwidth::from_gbps(2_000), Bandwidth::ZERO)
+        );
+        assert_eq!(
+            Bandwidth::from_mbps(999).split_at_unit(BandwidthUnit::Gbps),
+            (Bandwidth::ZERO, Bandwidth::from_mbps(999))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_human_string() {
+        assert_eq!(Bandwidth::new(1, 0).to_human_string(), "1Gbps");
+        assert_eq!(Bandwidth::new(0, 0).to_human_string(), "0bps");
+        assert_eq!(Bandwidth::new(1, 500_000_000).to_human_string(), "1.5Gbps");
+        assert_eq!(
+            Bandwidth::new(1, 500_000_000).to_human_decimal_string(),
+            "1.5Gbps"
+        );
+        assert_eq!(
+            Bandwidth::new(1, 500_000_000).to_human_integer_string(),
+            "1Gbps 500Mbps"
+        );
+        assert_eq!(
+            Bandwidth::new(9420, 0).to_human_integer_string(),
+            "9Tbps 420Gbps"
+        );
+    }
+}
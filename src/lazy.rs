@@ -0,0 +1,116 @@
+//! A formatted bandwidth that is only rendered once, on first use
+
+use crate::FormattedBandwidth;
+use alloc::string::{String, ToString};
+use bandwidth::Bandwidth;
+use core::cell::RefCell;
+use core::fmt;
+
+/// A [`FormattedBandwidth`] that renders its string on first display and
+/// reuses it afterwards
+///
+/// Constructing it does no formatting work, so it can be handed to code that
+/// may never display it, like a log statement below the current level.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_bandwidth, LazyFormattedBandwidth};
+///
+/// let val = LazyFormattedBandwidth::new(format_bandwidth(Bandwidth::new(0, 32_000_000)));
+/// assert!(!val.is_formatted());
+/// assert_eq!(val.to_string(), "32Mbps");
+/// assert!(val.is_formatted());
+/// ```
+pub struct LazyFormattedBandwidth {
+    inner: FormattedBandwidth,
+    cache: RefCell<Option<String>>,
+}
+
+impl LazyFormattedBandwidth {
+    /// Wraps `inner` without formatting it yet
+    pub fn new(inner: FormattedBandwidth) -> LazyFormattedBandwidth {
+        LazyFormattedBandwidth {
+            inner,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Returns a reference to the [`Bandwidth`] that is being formatted
+    pub fn get_ref(&self) -> &Bandwidth {
+        self.inner.get_ref()
+    }
+
+    /// Returns `true` once the string has been rendered
+    pub fn is_formatted(&self) -> bool {
+        self.cache.borrow().is_some()
+    }
+}
+
+impl From<FormattedBandwidth> for LazyFormattedBandwidth {
+    fn from(inner: FormattedBandwidth) -> LazyFormattedBandwidth {
+        LazyFormattedBandwidth::new(inner)
+    }
+}
+
+impl fmt::Debug for LazyFormattedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyFormattedBandwidth")
+            .field("bw", self.get_ref())
+            .field("formatted", &*self.cache.borrow())
+            .finish()
+    }
+}
+
+/// Displays like the wrapped [`FormattedBandwidth`], rendering it only the
+/// first time
+impl fmt::Display for LazyFormattedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.precision().is_some() || f.width().is_some() {
+            // the cached string is formatted without precision and padding
+            return fmt::Display::fmt(&self.inner, f);
+        }
+        if f.sign_plus() {
+            f.write_str("+")?;
+        }
+        let mut cache = self.cache.borrow_mut();
+        let s = cache.get_or_insert_with(|| self.inner.to_string());
+        f.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{format_bandwidth, format_bandwidth_with_options, FormatOptions};
+
+    #[test]
+    fn test_lazy() {
+        let val = LazyFormattedBandwidth::from(format_bandwidth_with_options(
+            Bandwidth::new(1, 500_000_000),
+            &FormatOptions::decimal(),
+        ));
+        assert!(!val.is_formatted());
+        assert_eq!(*val.get_ref(), Bandwidth::new(1, 500_000_000));
+        assert!(format!("{:?}", val).contains("formatted: None"));
+        assert!(!val.is_formatted());
+
+        assert_eq!(val.to_string(), "1.5Gbps");
+        assert!(val.is_formatted());
+        assert_eq!(format!("{:+}", val), "+1.5Gbps");
+        assert_eq!(val.to_string(), "1.5Gbps");
+        assert!(format!("{:?}", val).contains("formatted: Some(\"1.5Gbps\")"));
+        assert_eq!(format!("{:.2}", val), "1.50Gbps");
+        assert_eq!(format!("{:>9}", val), "  1.5Gbps");
+        assert_eq!(val.to_string(), "1.5Gbps");
+    }
+
+    #[test]
+    fn test_lazy_matches_eager() {
+        for bw in [Bandwidth::ZERO, Bandwidth::new(9420, 5), Bandwidth::MAX] {
+            let val = LazyFormattedBandwidth::new(format_bandwidth(bw));
+            assert_eq!(val.to_string(), format_bandwidth(bw).to_string());
+        }
+    }
+}
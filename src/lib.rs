@@ -3,30 +3,125 @@
 //! ## Facilities:
 //!
 //! * Parses bandwidth in free form like `2Gbps 340Mbps` or `2.34Gbps`
+//! * Keeps whole bits per second only: the fractional part of `1.9bps` is
+//!   truncated to `1bps` unless a [`RoundingMode`] is chosen
 //! * Formats bandwidth in similar form `150.024kbps` (default) or `150kbps 24bps` (with feature `display-integer` enabled)
 //!
 //! ## Features
 //!
 //! * Enable `serde` feature for serde integration.
+//! * Enable `serde_json` feature to convert to and from `serde_json::Value`.
 //! * Enable `display-integer` feature to display integer values only.
+//! * Enable `arrayvec` feature to format bandwidth without heap allocation.
+//! * Enable `packed` feature for [`PackedBandwidth`], a fixed 8-byte encoding.
+//! * Enable `linux-compat` feature to use the [`linux`] helpers on non-Linux targets.
+//! * Enable `rkyv` feature for zero-copy serialization of [`HumanBandwidth`].
+//! * Enable `humantime` feature to parse bandwidth and duration pairs like `1Gbps*10s`.
+//! * Enable `testing-utils` feature for the approximate comparisons and assertions of [`testing`].
+//! * Enable `bare-prefix` feature to accept a prefix without `bps`, like `10G` for 10Gbps.
+//! * Enable `clap` feature for `clap` value parsers, see [`HumanBandwidthParser`].
+//! * Enable `no-suggestions` feature to leave out the "did you mean" hints for unknown units.
+//! * Enable `schemars` feature for JSON schemas of the [`serde::Serde`] wrapper.
+//!
+//! ## `no_std`
+//!
+//! The `std` feature is enabled by default. Without it, the crate is
+//! `no_std` and parsing and formatting still work. The `alloc` feature
+//! (implied by `std`) adds the APIs returning a `String` or a `Vec`. Without
+//! `alloc`, the unit of [`Error::UnknownUnit`] is kept in a fixed size
+//! `UnitBuf` and there are no unit suggestions. The `serde`, `serde_json`,
+//! `clap`, `humantime`, `rkyv` and `schemars` features require `std`, while
+//! `testing-utils` works without it.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use core::str::{Chars, FromStr};
+#[cfg(feature = "std")]
 use std::error::Error as StdError;
-use std::fmt;
-use std::str::Chars;
 
+#[cfg(feature = "alloc")]
+mod batch;
+#[cfg(feature = "clap")]
+mod clap;
+#[cfg(feature = "humantime")]
+mod duration;
+mod ext;
+#[cfg(feature = "alloc")]
+mod lazy;
+#[cfg(feature = "packed")]
+mod packed;
+mod range;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(all(feature = "alloc", not(feature = "no-suggestions")))]
+mod suggest;
+mod sum;
+mod wrapper;
+
+#[cfg(any(target_os = "linux", feature = "linux-compat"))]
+pub mod linux;
 #[cfg(feature = "serde")]
 pub mod option;
 #[cfg(feature = "serde")]
 pub mod serde;
+#[cfg(feature = "serde")]
+pub mod serde_bps;
+pub mod standards;
+#[cfg(any(test, feature = "testing-utils"))]
+pub mod testing;
+pub mod util;
 
 /// Reexport module
 pub mod re {
     pub use bandwidth;
 }
 
+#[cfg(feature = "clap")]
+pub use crate::clap::{HumanBandwidthParser, HumanBandwidthRangeParser};
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::{from_archived, ArchivedHumanBandwidth};
+#[cfg(feature = "alloc")]
+pub use batch::BandwidthParser;
+#[cfg(feature = "humantime")]
+pub use duration::{
+    bandwidth_times_duration, parse_bandwidth_duration_pair, ParseBandwidthDurationError,
+};
+pub use ext::BandwidthExt;
+#[cfg(feature = "alloc")]
+pub use lazy::LazyFormattedBandwidth;
+#[cfg(feature = "packed")]
+pub use packed::PackedBandwidth;
+pub use range::{
+    parse_bandwidth_checked, parse_bandwidth_range, BandwidthCheckError, BandwidthRange,
+    HumanBandwidthRange,
+};
+#[cfg(feature = "std")]
+pub use reader::{
+    parse_bandwidth_from_reader, parse_bandwidth_from_reader_with_options, IoOrParseError,
+};
+#[cfg(all(feature = "alloc", not(feature = "no-suggestions")))]
+pub use suggest::suggest_unit;
+pub use sum::BandwidthSum;
+pub use wrapper::HumanBandwidth;
+
 use bandwidth::Bandwidth;
 
-const FRACTION_PART_LIMIT: u32 = 12;
+const FRACTION_PART_LIMIT: u32 = 18;
+const DEFAULT_FRACTION_DIGITS: u32 = 12;
 
 /// Error parsing human-friendly bandwidth
 #[derive(Debug, PartialEq, Clone)]
@@ -59,7 +154,11 @@ pub enum Error {
         /// End of the invalid unit inside the original string
         end: usize,
         /// The unit verbatim
+        #[cfg(feature = "alloc")]
         unit: String,
+        /// The unit verbatim, truncated to 32 bytes
+        #[cfg(not(feature = "alloc"))]
+        unit: UnitBuf,
         /// A number associated with the unit
         value: u64,
     },
@@ -69,8 +168,29 @@ pub enum Error {
     NumberOverflow,
     /// The value was an empty string (or consists only whitespace)
     Empty,
+    /// The bandwidth range is invalid
+    ///
+    /// Either the `..` (or legacy `-`) separator between the bounds is
+    /// missing, or the minimum is greater than the maximum.
+    InvalidRange,
+    /// There are more rate spans than allowed by
+    /// [`ParseOptions::max_spans`]
+    ///
+    /// The field is an byte offset of the first span over the limit.
+    TooManySpans(usize),
+    /// A unit on its own, as parsed into a [`BandwidthUnit`], is not one of
+    /// the allowed units
+    UnknownUnitName {
+        /// The unit verbatim
+        #[cfg(feature = "alloc")]
+        unit: String,
+        /// The unit verbatim, truncated to 32 bytes
+        #[cfg(not(feature = "alloc"))]
+        unit: UnitBuf,
+    },
 }
 
+#[cfg(feature = "std")]
 impl StdError for Error {}
 
 impl fmt::Display for Error {
@@ -85,23 +205,466 @@ impl fmt::Display for Error {
                     value,
                 )
             }
-            Error::UnknownUnit { unit, .. } => {
+            Error::UnknownUnit { unit, .. } if byte_unit_digits(unit).is_some() => write!(
+                f,
+                "unknown bandwidth unit {:?}, byte rates are only accepted \
+                with ParseOptions::allow_byte_units",
+                unit
+            ),
+            Error::UnknownUnit { unit, .. } | Error::UnknownUnitName { unit } => {
+                #[cfg(all(feature = "alloc", not(feature = "no-suggestions")))]
+                if let Some(suggestion) = suggest_unit(unit) {
+                    return write!(
+                        f,
+                        "unknown bandwidth unit {:?}, did you mean {:?}?",
+                        unit, suggestion
+                    );
+                }
                 write!(
                     f,
                     "unknown bandwidth unit {:?}, \
-                    supported units: bps, kbps, Mbps, Gbps, Tbps",
+                    supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps, \
+                    Bps, KBps, MBps, GBps, TBps",
                     unit
                 )
             }
             Error::NumberOverflow => write!(f, "number is too large"),
             Error::Empty => write!(f, "value was empty"),
+            Error::InvalidRange => write!(
+                f,
+                "invalid bandwidth range, expected `min..max` (or `min-max`) \
+                with min not greater than max"
+            ),
+            Error::TooManySpans(offset) => write!(f, "too many rate spans at {}", offset),
+        }
+    }
+}
+
+/// The unit of an [`Error::UnknownUnit`] without the `alloc` feature
+///
+/// Holds up to 32 bytes of the unit, longer units are truncated at a char
+/// boundary. Dereferences to `str`.
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnitBuf {
+    buf: [u8; 32],
+    len: u8,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl UnitBuf {
+    /// Returns the unit as a string slice
+    pub fn as_str(&self) -> &str {
+        // only whole chars are copied in `from`
+        core::str::from_utf8(&self.buf[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> From<&'a str> for UnitBuf {
+    fn from(s: &'a str) -> UnitBuf {
+        let mut len = s.len().min(32);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut buf = [0; 32];
+        buf[..len].copy_from_slice(&s.as_bytes()[..len]);
+        UnitBuf {
+            buf,
+            len: len as u8,
         }
     }
 }
 
+#[cfg(not(feature = "alloc"))]
+impl core::ops::Deref for UnitBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Debug for UnitBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
 /// A wrapper type that allows you to Display a Bandwidth
+///
+/// Comparisons and hashing only look at the wrapped bandwidth, not at the
+/// formatting options, so a list of values can be sorted directly and
+/// `1.5Gbps` equals `1Gbps 500Mbps`. A map keyed by `FormattedBandwidth`
+/// keeps the options of the first key inserted.
 #[derive(Debug, Clone)]
-pub struct FormattedBandwidth(Bandwidth);
+pub struct FormattedBandwidth {
+    bw: Bandwidth,
+    opts: FormatOptions,
+}
+
+impl PartialEq for FormattedBandwidth {
+    fn eq(&self, other: &FormattedBandwidth) -> bool {
+        self.bw == other.bw
+    }
+}
+
+impl Eq for FormattedBandwidth {}
+
+impl core::hash::Hash for FormattedBandwidth {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.bw.hash(state);
+    }
+}
+
+/// Zero bandwidth with the default options
+impl Default for FormattedBandwidth {
+    fn default() -> FormattedBandwidth {
+        format_bandwidth(Bandwidth::ZERO)
+    }
+}
+
+impl PartialOrd for FormattedBandwidth {
+    fn partial_cmp(&self, other: &FormattedBandwidth) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FormattedBandwidth {
+    fn cmp(&self, other: &FormattedBandwidth) -> core::cmp::Ordering {
+        self.bw.cmp(&other.bw)
+    }
+}
+
+/// Same as [`format_bandwidth`]
+impl From<Bandwidth> for FormattedBandwidth {
+    fn from(val: Bandwidth) -> FormattedBandwidth {
+        format_bandwidth(val)
+    }
+}
+
+impl<'a> From<&'a Bandwidth> for FormattedBandwidth {
+    fn from(val: &'a Bandwidth) -> FormattedBandwidth {
+        format_bandwidth(*val)
+    }
+}
+
+/// Whether bandwidth is formatted in decimal or integer form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatMode {
+    /// A single decimal value, e.g. `1.5Gbps`
+    Decimal,
+    /// Integer values for each unit, e.g. `1Gbps 500Mbps`
+    Integer,
+    /// Integer form for exact multiples of the unit, decimal form otherwise
+    ///
+    /// This differs from [`Decimal`][FormatMode::Decimal] only when a
+    /// precision is set: `1Gbps` stays `1Gbps` instead of `1.00Gbps`, while
+    /// `1.5Gbps` becomes `1.50Gbps`. See [`FormattedBandwidth::fmt_adaptive`].
+    Auto,
+    /// Up to three significant digits and a bare prefix, e.g. `1.5G`
+    ///
+    /// See [`FormattedBandwidth::fmt_compact`].
+    Compact,
+}
+
+/// [`Integer`][FormatMode::Integer] if the `display-integer` feature is
+/// enabled, [`Decimal`][FormatMode::Decimal] otherwise
+impl Default for FormatMode {
+    fn default() -> FormatMode {
+        #[cfg(not(feature = "display-integer"))]
+        return FormatMode::Decimal;
+        #[cfg(feature = "display-integer")]
+        return FormatMode::Integer;
+    }
+}
+
+/// Options to customize formatting of bandwidth
+///
+/// The options are set with builder-style methods, starting from
+/// [`FormatOptions::new`] (or [`FormatOptions::default`]), which is
+/// equivalent to the behaviour of [`format_bandwidth`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_bandwidth_with_options, BandwidthUnit, FormatMode, FormatOptions};
+///
+/// let opts = FormatOptions::new()
+///     .mode(FormatMode::Decimal)
+///     .unit(BandwidthUnit::Mbps)
+///     .separator(" ");
+/// let val = format_bandwidth_with_options(Bandwidth::new(1, 500_000_000), &opts);
+/// assert_eq!(val.to_string(), "1500 Mbps");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    mode: FormatMode,
+    unit: Option<BandwidthUnit>,
+    precision: Option<usize>,
+    separator: &'static str,
+}
+
+impl FormatOptions {
+    /// Creates the default options
+    pub fn new() -> FormatOptions {
+        FormatOptions {
+            mode: FormatMode::default(),
+            unit: None,
+            precision: None,
+            separator: "",
+        }
+    }
+
+    /// Creates the default options with [`FormatMode::Decimal`]
+    pub fn decimal() -> FormatOptions {
+        FormatOptions::new().mode(FormatMode::Decimal)
+    }
+
+    /// Creates the default options with [`FormatMode::Integer`]
+    pub fn integer() -> FormatOptions {
+        FormatOptions::new().mode(FormatMode::Integer)
+    }
+
+    /// Sets the formatting mode (default: [`FormatMode::Integer`] with the
+    /// `display-integer` feature, [`FormatMode::Decimal`] otherwise)
+    pub fn mode(mut self, mode: FormatMode) -> FormatOptions {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the unit to format in (default: the largest unit with a non-zero
+    /// value)
+    ///
+    /// In decimal mode the whole value is expressed in this unit, e.g.
+    /// `1500Mbps` rather than `1.5Gbps`. In integer mode this is the
+    /// largest unit used, smaller units follow as usual.
+    pub fn unit(mut self, unit: BandwidthUnit) -> FormatOptions {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Sets the exact number of fractional digits in decimal mode, rounding
+    /// half up (default: as many as needed, without trailing zeros)
+    ///
+    /// Ignored in integer mode. A precision in the format string, like
+    /// `{:.2}`, takes priority over this one. When rounding reaches 1000 of
+    /// the unit, the next unit is used, so `999.6Mbps` with a precision of 0
+    /// is `1Gbps`, unless [`unit`][FormatOptions::unit] is set.
+    pub fn precision(mut self, precision: usize) -> FormatOptions {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the string written between each number and its unit
+    /// (default: empty)
+    ///
+    /// Whitespace separators keep the output parseable by
+    /// [`parse_bandwidth`].
+    pub fn separator(mut self, separator: &'static str) -> FormatOptions {
+        self.separator = separator;
+        self
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions::new()
+    }
+}
+
+/// Options to customize parsing of human-friendly bandwidth
+///
+/// The options are set with builder-style methods, starting from
+/// [`ParseOptions::new`] (or [`ParseOptions::default`]), which is equivalent
+/// to the behaviour of [`parse_bandwidth`].
+///
+/// Parsing does not depend on the locale: the decimal separator is always
+/// `.`, whatever `LC_NUMERIC` says, and a `,` is rejected as an invalid
+/// character. Comma decimals are out of scope for the default parser; should
+/// they be supported, it will be through a dedicated option such as
+/// `decimal_separator(',')`, which is not implemented yet.
+///
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::ParseOptions;
+///
+/// let opts = ParseOptions::new().max_input_len(128);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    max_input_len: usize,
+    rounding_mode: RoundingMode,
+    allow_unlimited: bool,
+    allow_byte_units: bool,
+    allow_bare_number_as_bps: bool,
+    allow_bare_prefix: bool,
+    case_insensitive: bool,
+    max_fraction_digits: u32,
+    max_spans: Option<usize>,
+    pps_packet_size: Option<u16>,
+}
+
+/// How a fractional part finer than 1bps is handled while parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Drop the fractional part, `1.9bps` is parsed as `1bps` (default)
+    Floor,
+    /// Round half up, `1.5bps` is parsed as `2bps`
+    Round,
+    /// Round up any non-zero fraction, `1.1bps` is parsed as `2bps`
+    Ceil,
+}
+
+impl ParseOptions {
+    /// Creates the default options
+    pub fn new() -> ParseOptions {
+        ParseOptions {
+            max_input_len: 64,
+            rounding_mode: RoundingMode::Floor,
+            allow_unlimited: false,
+            allow_byte_units: true,
+            allow_bare_number_as_bps: false,
+            allow_bare_prefix: cfg!(feature = "bare-prefix"),
+            case_insensitive: false,
+            max_fraction_digits: DEFAULT_FRACTION_DIGITS,
+            max_spans: None,
+            pps_packet_size: None,
+        }
+    }
+
+    /// Sets the maximum number of bytes read by
+    /// [`parse_bandwidth_from_reader_with_options`] (default: 64)
+    pub fn max_input_len(mut self, len: usize) -> ParseOptions {
+        self.max_input_len = len;
+        self
+    }
+
+    /// Sets how a fractional part finer than 1bps is handled
+    /// (default: [`RoundingMode::Floor`])
+    ///
+    /// Only the first [`max_fraction_digits`][ParseOptions::max_fraction_digits]
+    /// fractional digits are taken into account.
+    pub fn rounding_mode(mut self, mode: RoundingMode) -> ParseOptions {
+        self.rounding_mode = mode;
+        self
+    }
+
+    /// Sets whether the keywords `unlimited`, `inf`, `infinity`, `∞` and
+    /// `max` are accepted (default: `false`)
+    ///
+    /// The keywords are matched case-insensitively and map to
+    /// [`Bandwidth::MAX`], the sentinel for an unlimited bandwidth. See also
+    /// [`format_bandwidth_or_unlimited`] and `serde::unlimited` (with the
+    /// `serde` feature).
+    pub fn allow_unlimited(mut self, allow: bool) -> ParseOptions {
+        self.allow_unlimited = allow;
+        self
+    }
+
+    /// Sets whether the byte rate units `Bps`, `kBps`, `MBps`, `GBps` and
+    /// `TBps` are accepted (default: `true`)
+    ///
+    /// A byte rate is converted to bps by multiplying it by 8, so `1GBps` is
+    /// 8Gbps. The `B/s` spellings (`MB/s`, ...) are accepted as well.
+    /// Fractions of a byte are kept, `1.5Bps` is 12bps, and only the part
+    /// below 1bps is subject to [`ParseOptions::rounding_mode`]. Since a capital `B` is easily
+    /// mistaken for a bit, strict parsers may want to turn this off.
+    pub fn allow_byte_units(mut self, allow: bool) -> ParseOptions {
+        self.allow_byte_units = allow;
+        self
+    }
+
+    /// Sets whether a number without a unit is read as bps
+    /// (default: `false`)
+    ///
+    /// With this set, `1000` parses as 1000bps and `1Mbps 500` as
+    /// 1.0000005Mbps.
+    pub fn allow_bare_number_as_bps(mut self, allow: bool) -> ParseOptions {
+        self.allow_bare_number_as_bps = allow;
+        self
+    }
+
+    /// Sets whether a prefix without `bps` is accepted, like `10G` for
+    /// 10Gbps (default: `true` with the `bare-prefix` feature, `false`
+    /// otherwise)
+    ///
+    /// The prefixes are `k`, `M`, `G`, `T`, `P` and `E`, in either case.
+    pub fn allow_bare_prefix(mut self, allow: bool) -> ParseOptions {
+        self.allow_bare_prefix = allow;
+        self
+    }
+
+    /// Sets whether bit rate units are matched case-insensitively, so
+    /// `GBPS` and `GBPs` are gigabits per second (default: `false`)
+    ///
+    /// Even by default the case of the prefix does not matter, `Kbps` and
+    /// `kbps` are the same. The exact byte rate spellings like `MBps` and
+    /// `MB/s` keep their meaning, any other spelling is folded to a bit rate
+    /// unit: `MBPS` is a megabit per second.
+    pub fn case_insensitive(mut self, enable: bool) -> ParseOptions {
+        self.case_insensitive = enable;
+        self
+    }
+
+    /// Sets the number of fractional digits taken into account, further
+    /// digits are ignored (default: 12, maximum: 18)
+    ///
+    /// 12 digits resolve 1Mbps in `Ebps`, 18 digits are enough to express
+    /// 1bps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is greater than 18.
+    pub fn max_fraction_digits(mut self, digits: u32) -> ParseOptions {
+        assert!(
+            digits <= FRACTION_PART_LIMIT,
+            "at most 18 fraction digits are supported"
+        );
+        self.max_fraction_digits = digits;
+        self
+    }
+
+    /// Sets the maximum number of rate spans, like `1Gbps` in
+    /// `1Gbps 500Mbps`, failing with [`Error::TooManySpans`] if there are
+    /// more (default: no limit)
+    pub fn max_spans(mut self, max: usize) -> ParseOptions {
+        self.max_spans = Some(max);
+        self
+    }
+
+    /// Enables the packet rate units `pps`, `kpps` and `Mpps`, assuming
+    /// packets of `size` bytes (default: disabled)
+    ///
+    /// A packet rate is converted to bps by multiplying it by `size * 8`, so
+    /// `1Mpps` with 1500 byte packets is 12Gbps. Fractions of a packet are
+    /// kept, only the part below 1bps is subject to
+    /// [`ParseOptions::rounding_mode`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn pps_packet_size_bytes(mut self, size: u16) -> ParseOptions {
+        assert!(size > 0, "packet size must be non-zero");
+        self.pps_packet_size = Some(size);
+        self
+    }
+}
+
+impl Default for RoundingMode {
+    fn default() -> RoundingMode {
+        RoundingMode::Floor
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions::new()
+    }
+}
 
 trait OverflowOp: Sized {
     fn mul(self, other: Self) -> Result<Self, Error>;
@@ -117,11 +680,71 @@ impl OverflowOp for u64 {
     }
 }
 
-fn parse_fraction(fraction: u64, fraction_cnt: u32, need_digit: u32) -> u64 {
+/// Canonical spellings of the bit rate units, preferred ones first
+const BIT_UNITS: &[&str] = &[
+    "bps", "kbps", "Mbps", "Gbps", "Tbps", "Pbps", "Ebps", "bit/s", "kbit/s", "Mbit/s", "Gbit/s",
+    "Tbit/s", "Pbit/s", "Ebit/s", "b/s", "kb/s", "Mb/s", "Gb/s", "Tb/s", "Pb/s", "Eb/s",
+];
+
+/// Returns the power of ten of a byte rate unit, if `unit` is one
+fn byte_unit_digits(unit: &str) -> Option<u32> {
+    match unit {
+        "Bps" | "B/s" => Some(0),
+        "kBps" | "KBps" | "kB/s" | "KB/s" => Some(3),
+        "MBps" | "MB/s" => Some(6),
+        "GBps" | "GB/s" => Some(9),
+        "TBps" | "TB/s" => Some(12),
+        _ => None,
+    }
+}
+
+/// Splits a number of bits per second into gbps and the remaining bps
+fn split_bits(bits: u128) -> Result<(u64, u64), Error> {
+    let gbps = u64::try_from(bits / 1_000_000_000).map_err(|_| Error::NumberOverflow)?;
+    Ok((gbps, (bits % 1_000_000_000) as u64))
+}
+
+/// Multiplies the number `n.fraction` by `multiplier` bits, splitting the
+/// result into gbps and the remaining bps
+///
+/// The fraction is multiplied before it is scaled down, so only the part
+/// below 1bps is rounded with `mode`.
+fn scale_to_bits(
+    n: u64,
+    fraction: u64,
+    fraction_cnt: u32,
+    multiplier: u128,
+    mode: RoundingMode,
+) -> Result<(u64, u64), Error> {
+    // `n` is below 2^64 and `multiplier` below 2^64, so neither overflows
+    let whole = n as u128 * multiplier;
+    let frac = fraction as u128 * multiplier;
+    let div = 10u128.pow(fraction_cnt);
+    let (q, r) = (frac / div, frac % div);
+    let bits = match mode {
+        RoundingMode::Round if r > 0 && r >= div - r => q + 1,
+        RoundingMode::Ceil if r > 0 => q + 1,
+        _ => q,
+    };
+    split_bits(whole + bits)
+}
+
+/// Scales the `fraction_cnt` digits of `fraction` to `need_digit` digits
+///
+/// With `need_digit == 0` (the `bps` unit) only the sub-bps part is left, so
+/// the result is `0`, or `1` if `mode` rounds it up.
+fn parse_fraction(fraction: u64, fraction_cnt: u32, need_digit: u32, mode: RoundingMode) -> u64 {
     if need_digit >= fraction_cnt {
         fraction * 10u64.pow(need_digit - fraction_cnt)
     } else {
-        fraction / 10u64.pow(fraction_cnt - need_digit)
+        let div = 10u64.pow(fraction_cnt - need_digit);
+        let (q, r) = (fraction / div, fraction % div);
+        match mode {
+            RoundingMode::Floor => q,
+            RoundingMode::Round if r >= div - r => q + 1,
+            RoundingMode::Ceil if r > 0 => q + 1,
+            _ => q,
+        }
     }
 }
 
@@ -129,9 +752,30 @@ struct Parser<'a> {
     iter: Chars<'a>,
     src: &'a str,
     current: (u64, u64),
+    opts: ParseOptions,
+    /// Offset of the first digit of the number being parsed
+    num_start: usize,
+    /// Each rate span, if requested
+    #[cfg(feature = "alloc")]
+    spans: Option<Vec<ParsedSpan>>,
+    /// Number of rate spans parsed so far
+    span_cnt: usize,
 }
 
-impl Parser<'_> {
+impl<'a> Parser<'a> {
+    fn new(s: &'a str, opts: &ParseOptions) -> Parser<'a> {
+        Parser {
+            iter: s.chars(),
+            src: s,
+            current: (0, 0),
+            opts: *opts,
+            num_start: 0,
+            #[cfg(feature = "alloc")]
+            spans: None,
+            span_cnt: 0,
+        }
+    }
+
     fn off(&self) -> usize {
         self.src.len() - self.iter.as_str().len()
     }
@@ -141,6 +785,7 @@ impl Parser<'_> {
         for c in self.iter.by_ref() {
             match c {
                 '0'..='9' => {
+                    self.num_start = self.off() - 1;
                     return Ok(Some(c as u64 - '0' as u64));
                 }
                 c if c.is_whitespace() => continue,
@@ -160,36 +805,131 @@ impl Parser<'_> {
         start: usize,
         end: usize,
     ) -> Result<(), Error> {
-        let (mut gbps, bps) = match &self.src[start..end] {
-            "bps" | "bit/s" | "b/s" => (0u64, n),
-            "kbps" | "Kbps" | "kbit/s" | "Kbit/s" | "kb/s" | "Kb/s" => (
+        if matches!(self.opts.max_spans, Some(max) if self.span_cnt >= max) {
+            return Err(Error::TooManySpans(self.num_start));
+        }
+        self.span_cnt += 1;
+        let mode = self.opts.rounding_mode;
+        let unit = &self.src[start..end];
+        let unit = match unit {
+            "" if self.opts.allow_bare_number_as_bps => "bps",
+            unit => unit,
+        };
+        let unit = match unit {
+            "k" | "K" if self.opts.allow_bare_prefix => "kbps",
+            "m" | "M" if self.opts.allow_bare_prefix => "Mbps",
+            "g" | "G" if self.opts.allow_bare_prefix => "Gbps",
+            "t" | "T" if self.opts.allow_bare_prefix => "Tbps",
+            "p" | "P" if self.opts.allow_bare_prefix => "Pbps",
+            "e" | "E" if self.opts.allow_bare_prefix => "Ebps",
+            unit => unit,
+        };
+        let unit = match unit {
+            unit if self.opts.case_insensitive && byte_unit_digits(unit).is_none() => BIT_UNITS
+                .iter()
+                .find(|u| u.eq_ignore_ascii_case(unit))
+                .map_or(unit, |u| *u),
+            unit => unit,
+        };
+        let (mut gbps, bps, prefix) = match (BandwidthUnit::from_alias(unit), unit) {
+            (Some(BandwidthUnit::Bps), _) => (
+                0u64,
+                n.add(parse_fraction(fraction, fraction_cnt, 0, mode))?,
+                BandwidthUnit::Bps,
+            ),
+            (Some(BandwidthUnit::Kbps), _) => (
                 0u64,
                 n.mul(1000)?
-                    .add(parse_fraction(fraction, fraction_cnt, 3))?,
+                    .add(parse_fraction(fraction, fraction_cnt, 3, mode))?,
+                BandwidthUnit::Kbps,
             ),
-            "Mbps" | "mbps" | "Mbit/s" | "mbit/s" | "Mb/s" | "mb/s" => (
+            (Some(BandwidthUnit::Mbps), _) => (
                 0u64,
                 n.mul(1_000_000)?
-                    .add(parse_fraction(fraction, fraction_cnt, 6))?,
+                    .add(parse_fraction(fraction, fraction_cnt, 6, mode))?,
+                BandwidthUnit::Mbps,
             ),
-            "Gbps" | "gbps" | "Gbit/s" | "gbit/s" | "Gb/s" | "gb/s" => {
-                (n, parse_fraction(fraction, fraction_cnt, 9))
+            (Some(BandwidthUnit::Gbps), _) => (
+                n,
+                parse_fraction(fraction, fraction_cnt, 9, mode),
+                BandwidthUnit::Gbps,
+            ),
+            (Some(BandwidthUnit::Tbps), _) => {
+                let bps = parse_fraction(fraction, fraction_cnt, 12, mode);
+                (
+                    n.mul(1000)?.add(bps / 1_000_000_000)?,
+                    bps % 1_000_000_000,
+                    BandwidthUnit::Tbps,
+                )
             }
-            "Tbps" | "tbps" | "Tbit/s" | "tbit/s" | "Tb/s" | "tb/s" => {
-                let bps = parse_fraction(fraction, fraction_cnt, 12);
-                (n.mul(1000)?.add(bps / 1_000_000_000)?, bps % 1_000_000_000)
+            (Some(BandwidthUnit::Pbps), _) => {
+                let bps = parse_fraction(fraction, fraction_cnt, 15, mode);
+                (
+                    n.mul(1_000_000)?.add(bps / 1_000_000_000)?,
+                    bps % 1_000_000_000,
+                    BandwidthUnit::Pbps,
+                )
+            }
+            (Some(BandwidthUnit::Ebps), _) => {
+                let bps = parse_fraction(fraction, fraction_cnt, 18, mode);
+                (
+                    n.mul(1_000_000_000)?.add(bps / 1_000_000_000)?,
+                    bps % 1_000_000_000,
+                    BandwidthUnit::Ebps,
+                )
+            }
+            (_, unit @ ("pps" | "kpps" | "Mpps")) if self.opts.pps_packet_size.is_some() => {
+                let digits = match unit {
+                    "pps" => 0,
+                    "kpps" => 3,
+                    _ => 6,
+                };
+                let size = self.opts.pps_packet_size.unwrap_or(1) as u128;
+                let (gbps, bps) = scale_to_bits(
+                    n,
+                    fraction,
+                    fraction_cnt,
+                    size * 8 * 10u128.pow(digits),
+                    mode,
+                )?;
+                (gbps, bps, BandwidthUnit::ALL[digits as usize / 3])
+            }
+            (_, unit) if self.opts.allow_byte_units && byte_unit_digits(unit).is_some() => {
+                let digits = byte_unit_digits(unit).unwrap_or(0);
+                let (gbps, bps) =
+                    scale_to_bits(n, fraction, fraction_cnt, 8 * 10u128.pow(digits), mode)?;
+                (gbps, bps, BandwidthUnit::ALL[digits as usize / 3])
             }
             _ => {
                 return Err(Error::UnknownUnit {
                     start,
                     end,
-                    unit: self.src[start..end].to_string(),
+                    unit: self.src[start..end].into(),
                     value: n,
                 });
             }
         };
+        let mut bps = bps;
+        if bps >= 1_000_000_000 {
+            gbps = gbps.add(bps / 1_000_000_000)?;
+            bps %= 1_000_000_000;
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = prefix;
+        #[cfg(feature = "alloc")]
+        if let Some(spans) = &mut self.spans {
+            spans.push(ParsedSpan {
+                value: n,
+                fraction,
+                fraction_digits: fraction_cnt,
+                unit: prefix,
+                start: self.num_start,
+                end,
+                bandwidth: Bandwidth::new(gbps, bps as u32),
+            });
+        }
         let mut bps = self.current.1.add(bps)?;
-        if bps > 1_000_000_000 {
+        if bps >= 1_000_000_000 {
             gbps = gbps.add(bps / 1_000_000_000)?;
             bps %= 1_000_000_000;
         }
@@ -198,25 +938,29 @@ impl Parser<'_> {
         Ok(())
     }
 
-    fn parse(mut self) -> Result<Bandwidth, Error> {
+    fn parse(&mut self) -> Result<Bandwidth, Error> {
         let mut n = self.parse_first_char()?.ok_or(Error::Empty)?;
         let mut decimal = false;
         let mut fraction: u64 = 0;
         let mut fraction_cnt: u32 = 0;
         'outer: loop {
             let mut off = self.off();
+            // whether the previous char is a digit, the number starts with one
+            let mut after_digit = true;
             while let Some(c) = self.iter.next() {
+                let is_digit = c.is_ascii_digit();
                 match c {
                     '0'..='9' => {
                         if decimal {
-                            if fraction_cnt >= FRACTION_PART_LIMIT {
-                                continue;
+                            // digits past the limit are dropped, but still
+                            // advance `off` so the unit starts after them
+                            if fraction_cnt < self.opts.max_fraction_digits {
+                                fraction = fraction
+                                    .checked_mul(10)
+                                    .and_then(|x| x.checked_add(c as u64 - '0' as u64))
+                                    .ok_or(Error::NumberOverflow)?;
+                                fraction_cnt += 1;
                             }
-                            fraction = fraction
-                                .checked_mul(10)
-                                .and_then(|x| x.checked_add(c as u64 - '0' as u64))
-                                .ok_or(Error::NumberOverflow)?;
-                            fraction_cnt += 1;
                         } else {
                             n = n
                                 .checked_mul(10)
@@ -231,6 +975,13 @@ impl Parser<'_> {
                         }
                         decimal = true;
                     }
+                    // a digit separator, only between two digits
+                    '_' if after_digit
+                        && self
+                            .iter
+                            .clone()
+                            .next()
+                            .map_or(false, |c| c.is_ascii_digit()) => {}
                     'a'..='z' | 'A'..='Z' | '/' => {
                         break;
                     }
@@ -238,6 +989,7 @@ impl Parser<'_> {
                         return Err(Error::InvalidCharacter(off));
                     }
                 }
+                after_digit = is_digit;
                 off = self.off();
             }
             let start = off;
@@ -246,6 +998,7 @@ impl Parser<'_> {
                 match c {
                     '0'..='9' => {
                         self.parse_unit(n, fraction, fraction_cnt, start, off)?;
+                        self.num_start = off;
                         n = c as u64 - '0' as u64;
                         fraction = 0;
                         decimal = false;
@@ -282,9 +1035,38 @@ impl Parser<'_> {
 /// * `Mbps`, `Mbit/s`, `Mb/s` -- megabit per second
 /// * `Gbps`, `Gbit/s`, `Gb/s` -- gigabit per second
 /// * `Tbps`, `Tbit/s`, `Tb/s` -- terabit per second
+/// * `Pbps`, `Pbit/s`, `Pb/s` -- petabit per second
+/// * `Ebps`, `Ebit/s`, `Eb/s` -- exabit per second
+/// * `Bps`, `B/s` -- byte per second, i.e. 8bps
+/// * `KBps`, `kBps`, `KB/s`, `kB/s` -- kilobyte per second
+/// * `MBps`, `MB/s` -- megabyte per second
+/// * `GBps`, `GB/s` -- gigabyte per second
+/// * `TBps`, `TB/s` -- terabyte per second
+///
+/// The byte units need a capital `B`: `mbps` is a megabit per second, not a
+/// megabyte. They can be turned off with [`ParseOptions::allow_byte_units`].
+///
+/// With the `bare-prefix` feature, the prefix alone is accepted as well:
+/// `10G` is 10Gbps, and `k`, `M`, `T`, `P` and `E` work the same way. `B` is
+/// never accepted as it would read as bytes. See also
+/// [`ParseOptions::allow_bare_prefix`].
+///
+/// The prefixes are always SI powers of 1000, and the case of the prefix
+/// letter does not matter: `1Kbps` and `1kbps` are both 1000bps, and `1mbps`
+/// is the same as `1Mbps`. Powers of 1024 are never used.
 ///
 /// While the number can be integer or decimal, the fractional part less than 1bps will always be
-/// ignored.
+/// ignored. Use [`parse_bandwidth_with_options`] with [`ParseOptions::rounding_mode`] to round it
+/// instead.
+///
+/// Whitespace between the digits of a number is ignored, so a space can be
+/// used as a thousands separator: `1 000Mbps` is the same as `1000Mbps`.
+/// An underscore can be used the same way, like in Rust literals:
+/// `1_000_000bps` is 1Mbps. It must sit between two digits, so `1_Mbps`,
+/// `1__000kbps` and `1_.5Gbps` fail with [`Error::InvalidCharacter`], and `_1Mbps` with
+/// [`Error::NumberExpected`].
+///
+/// The decimal separator is always `.`, independent of the locale.
 ///
 /// # Examples
 ///
@@ -299,395 +1081,3285 @@ impl Parser<'_> {
 /// assert_eq!(parse_bandwidth("150.02456kbps"), Ok(Bandwidth::new(0, 150_024)));
 /// ```
 pub fn parse_bandwidth(s: &str) -> Result<Bandwidth, Error> {
-    Parser {
-        iter: s.chars(),
-        src: s,
-        current: (0, 0),
-    }
-    .parse()
+    parse_bandwidth_with_options(s, &ParseOptions::default())
 }
 
-/// Formats bandwidth into a human-readable string
+/// Checks that `s` is a valid bandwidth for [`parse_bandwidth`], without
+/// returning it
 ///
-/// Note: this format is guaranteed to have same value when using
-/// parse_bandwidth, but we can change some details of the exact composition
-/// of the value.
+/// The same checks are made, units and digits alike, and a total which
+/// would overflow [`Bandwidth`] is an [`Error::NumberOverflow`] here too.
+/// Like `parse_bandwidth` this never allocates unless it fails, so it is
+/// only marginally faster; it is meant for form validation and completion,
+/// where the value itself is not needed.
 ///
-/// By default it will format the value with the largest possible unit in decimal form.
-/// If you want to display integer values only, enable the `display-integer` feature.
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::{validate_bandwidth_string, Error};
+///
+/// assert_eq!(validate_bandwidth_string("9Tbps 420Gbps"), Ok(()));
+/// assert!(validate_bandwidth_string("1Gbit").is_err());
+/// assert_eq!(validate_bandwidth_string(""), Err(Error::Empty));
+/// ```
+pub fn validate_bandwidth_string(s: &str) -> Result<(), Error> {
+    Parser::new(s, &ParseOptions::default()).parse().map(|_| ())
+}
+
+/// Parse bandwidth object like [`parse_bandwidth`], from a `Cow<str>`
 ///
 /// # Examples
 ///
 /// ```
 /// use bandwidth::Bandwidth;
-/// use human_bandwidth::format_bandwidth;
+/// use human_bandwidth::parse_bandwidth_cow;
+/// use std::borrow::Cow;
 ///
-/// // Enabling the `display-integer` feature will display integer values only
-/// # #[cfg(feature = "display-integer")]
-/// # {
-/// let val1 = Bandwidth::new(9420, 0);
-/// assert_eq!(format_bandwidth(val1).to_string(), "9Tbps 420Gbps");
-/// let val2 = Bandwidth::new(0, 32_000_000);
-/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
-/// # }
-///
-/// // Disabling the `display-integer` feature will display decimal values
-/// # #[cfg(not(feature = "display-integer"))]
-/// # {
-/// let val1 = Bandwidth::new(9420, 0);
-/// assert_eq!(format_bandwidth(val1).to_string(), "9.42Tbps");
-/// let val2 = Bandwidth::new(0, 32_000_000);
-/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
-/// # }
+/// let s: Cow<str> = String::from_utf8_lossy(b"32Mbps");
+/// assert_eq!(parse_bandwidth_cow(s), Ok(Bandwidth::new(0, 32_000_000)));
 /// ```
-pub fn format_bandwidth(val: Bandwidth) -> FormattedBandwidth {
-    FormattedBandwidth(val)
+#[cfg(feature = "alloc")]
+pub fn parse_bandwidth_cow(s: Cow<str>) -> Result<Bandwidth, Error> {
+    parse_bandwidth(&s)
 }
 
-fn item(f: &mut fmt::Formatter, started: &mut bool, name: &str, value: u32) -> fmt::Result {
-    if value > 0 {
-        if *started {
-            f.write_str(" ")?;
+/// Parse bandwidth object like [`parse_bandwidth`], with custom options
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_bandwidth_with_options, ParseOptions, RoundingMode};
+///
+/// let opts = ParseOptions::new().rounding_mode(RoundingMode::Round);
+/// assert_eq!(parse_bandwidth_with_options("1.9bps", &opts), Ok(Bandwidth::from_bps(2)));
+/// assert_eq!(parse_bandwidth_with_options("150.0245kbps", &opts), Ok(Bandwidth::new(0, 150_025)));
+///
+/// let opts = ParseOptions::new().allow_unlimited(true);
+/// assert_eq!(parse_bandwidth_with_options("unlimited", &opts), Ok(Bandwidth::MAX));
+/// ```
+pub fn parse_bandwidth_with_options(s: &str, opts: &ParseOptions) -> Result<Bandwidth, Error> {
+    if opts.allow_unlimited {
+        let keyword = s.trim();
+        if ["unlimited", "inf", "infinity", "∞", "max"]
+            .iter()
+            .any(|k| keyword.eq_ignore_ascii_case(k))
+        {
+            return Ok(Bandwidth::MAX);
         }
-        write!(f, "{}{}", value, name)?;
-        *started = true;
     }
-    Ok(())
-}
-
-#[derive(Copy, Clone)]
-#[repr(usize)]
-enum LargestUnit {
-    Bps = 0,
-    Kbps = 1,
-    Mbps = 2,
-    Gbps = 3,
-    Tbps = 4,
+    Parser::new(s, opts).parse()
 }
 
-impl fmt::Display for LargestUnit {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            LargestUnit::Bps => f.write_str("bps"),
-            LargestUnit::Kbps => f.write_str("kbps"),
-            LargestUnit::Mbps => f.write_str("Mbps"),
-            LargestUnit::Gbps => f.write_str("Gbps"),
-            LargestUnit::Tbps => f.write_str("Tbps"),
-        }
-    }
+/// A rate span of a parsed bandwidth, like `1.5Gbps` in `1.5Gbps 2kbps`
+///
+/// Returned by [`parse_bandwidth_spans`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsedSpan {
+    /// The integer part of the number
+    pub value: u64,
+    /// The fractional part of the number, as written
+    pub fraction: u64,
+    /// The number of digits of `fraction`, so `.05` has a `fraction` of 5
+    /// and 2 `fraction_digits`
+    pub fraction_digits: u32,
+    /// The unit with the same prefix as the span
+    ///
+    /// Byte and packet rates map to the unit with the same SI prefix: the unit of
+    /// `1MBps` is [`BandwidthUnit::Mbps`], while its `bandwidth` is 8Mbps.
+    pub unit: BandwidthUnit,
+    /// Byte offset of the first digit of the number
+    pub start: usize,
+    /// Byte offset of the end (exclusive) of the unit
+    pub end: usize,
+    /// The bandwidth of the span
+    pub bandwidth: Bandwidth,
 }
 
-impl FormattedBandwidth {
-    /// Returns a reference to the [`Bandwidth`][] that is being formatted.
-    pub fn get_ref(&self) -> &Bandwidth {
-        &self.0
+/// Parse bandwidth object like [`parse_bandwidth`], returning each rate span
+/// before they are summed up
+///
+/// The spans are in the order they appear in `s`, and their bandwidths add
+/// up to `parse_bandwidth(s)`. `&s[span.start..span.end]` is the text of a
+/// span. This lets lint tools flag redundant or oddly ordered spans.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_bandwidth_spans, BandwidthUnit};
+///
+/// let s = "2Gbps 340.5Mbps";
+/// let spans = parse_bandwidth_spans(s).unwrap();
+/// assert_eq!(spans.len(), 2);
+/// assert_eq!(spans[0].unit, BandwidthUnit::Gbps);
+/// assert_eq!(spans[1].bandwidth, Bandwidth::from_kbps(340_500));
+/// assert_eq!((spans[1].value, spans[1].fraction, spans[1].fraction_digits), (340, 5, 1));
+/// assert_eq!(&s[spans[1].start..spans[1].end], "340.5Mbps");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn parse_bandwidth_spans(s: &str) -> Result<Vec<ParsedSpan>, Error> {
+    let mut parser = Parser::new(s, &ParseOptions::default());
+    parser.spans = Some(Vec::new());
+    parser.parse()?;
+    Ok(parser.spans.unwrap_or_default())
+}
+
+/// Parse bandwidth with [`parse_bandwidth`], returning `default` on any error
+///
+/// The error is swallowed silently, so this is not appropriate where invalid
+/// input should be reported, such as config validation.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_or_default;
+///
+/// let default = Bandwidth::from_mbps(100);
+/// assert_eq!(parse_bandwidth_or_default("1Gbps", default), Bandwidth::from_gbps(1));
+/// assert_eq!(parse_bandwidth_or_default("1Gbit", default), default);
+/// ```
+#[inline]
+pub fn parse_bandwidth_or_default(s: &str, default: Bandwidth) -> Bandwidth {
+    parse_bandwidth(s).unwrap_or(default)
+}
+
+/// Parse bandwidth with [`parse_bandwidth`], returning zero on any error
+///
+/// See [`parse_bandwidth_or_default`].
+#[inline]
+pub fn parse_bandwidth_or_zero(s: &str) -> Bandwidth {
+    parse_bandwidth_or_default(s, Bandwidth::ZERO)
+}
+
+/// Parse bandwidth with [`parse_bandwidth`], returning [`Bandwidth::MAX`] on
+/// any error
+///
+/// See [`parse_bandwidth_or_default`].
+#[inline]
+pub fn parse_bandwidth_or_max(s: &str) -> Bandwidth {
+    parse_bandwidth_or_default(s, Bandwidth::MAX)
+}
+
+/// Parse bandwidth object like [`parse_bandwidth`], reading a bare integer
+/// as bits per second
+///
+/// Tools that print raw throughput, like `1048576`, can be read without adding
+/// a unit first. Input accepted by [`parse_bandwidth`] parses to the same
+/// bandwidth. This is [`ParseOptions::allow_bare_number_as_bps`] with the
+/// default options otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_relaxed;
+///
+/// assert_eq!(parse_bandwidth_relaxed("1048576"), Ok(Bandwidth::from_bps(1_048_576)));
+/// assert_eq!(parse_bandwidth_relaxed("32Mbps"), Ok(Bandwidth::from_mbps(32)));
+/// ```
+pub fn parse_bandwidth_relaxed(s: &str) -> Result<Bandwidth, Error> {
+    parse_bandwidth_with_options(s, &ParseOptions::new().allow_bare_number_as_bps(true))
+}
+
+/// Parse bandwidth object in the compact form of [`format_bandwidth_compact`]
+///
+/// A prefix without `bps` is accepted, like `1.5G`, and a bare number is
+/// read as bps. The other forms of [`parse_bandwidth`] are accepted as well.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_compact;
+///
+/// assert_eq!(parse_bandwidth_compact("1.5G"), Ok(Bandwidth::new(1, 500_000_000)));
+/// assert_eq!(parse_bandwidth_compact("250k"), Ok(Bandwidth::from_kbps(250)));
+/// assert_eq!(parse_bandwidth_compact("12"), Ok(Bandwidth::from_bps(12)));
+/// ```
+pub fn parse_bandwidth_compact(s: &str) -> Result<Bandwidth, Error> {
+    let opts = ParseOptions::new()
+        .allow_bare_prefix(true)
+        .allow_bare_number_as_bps(true);
+    parse_bandwidth_with_options(s, &opts)
+}
+
+/// Parse bandwidth object like [`parse_bandwidth`], matching bit rate units
+/// case-insensitively
+///
+/// See [`ParseOptions::case_insensitive`]: `1GBPS` is 1Gbps, while the byte
+/// rate `1GBps` is still 8Gbps.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_case_insensitive;
+///
+/// assert_eq!(parse_bandwidth_case_insensitive("1GBPS"), Ok(Bandwidth::from_gbps(1)));
+/// assert_eq!(parse_bandwidth_case_insensitive("1GBPs"), Ok(Bandwidth::from_gbps(1)));
+/// assert_eq!(parse_bandwidth_case_insensitive("1GBps"), Ok(Bandwidth::from_gbps(8)));
+/// ```
+pub fn parse_bandwidth_case_insensitive(s: &str) -> Result<Bandwidth, Error> {
+    parse_bandwidth_with_options(s, &ParseOptions::new().case_insensitive(true))
+}
+
+/// Returns the end of the rate span starting at `start`: a number (which may
+/// contain whitespace) followed by a unit
+fn span_end(s: &str, start: usize) -> usize {
+    let mut in_unit = false;
+    for (i, c) in s[start..].char_indices() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '/' => in_unit = true,
+            '0'..='9' | '.' | '_' if !in_unit => {}
+            c if c.is_whitespace() && !in_unit => {}
+            _ => return start + i,
+        }
+    }
+    s.len()
+}
+
+/// Parse bandwidth object from the start of `s`, returning the unparsed tail
+///
+/// Rate spans are consumed like in [`parse_bandwidth`] for as long as they
+/// parse. The first span that does not (for example `10s` or `, next`) and
+/// everything after it is returned untouched as the tail, including any
+/// whitespace in front of it. An error is returned only if the very first
+/// span cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_partial;
+///
+/// assert_eq!(
+///     parse_bandwidth_partial("1Gbps 500Mbps 10s"),
+///     Ok((Bandwidth::new(1, 500_000_000), " 10s"))
+/// );
+/// assert_eq!(parse_bandwidth_partial("32Mbps"), Ok((Bandwidth::from_mbps(32), "")));
+/// ```
+pub fn parse_bandwidth_partial(s: &str) -> Result<(Bandwidth, &str), Error> {
+    let mut total: Option<Bandwidth> = None;
+    let mut start = 0;
+    while start < s.len() {
+        let end = span_end(s, start);
+        let span = match parse_bandwidth(&s[start..end]) {
+            Ok(span) => span,
+            Err(_) if total.is_some() => break,
+            Err(Error::Empty) if end < s.len() => return Err(Error::NumberExpected(end)),
+            Err(e) => return Err(e),
+        };
+        total = Some(match total {
+            Some(total) => total.checked_add(span).ok_or(Error::NumberOverflow)?,
+            None => span,
+        });
+        start = end;
+    }
+    total.map(|bw| (bw, &s[start..])).ok_or(Error::Empty)
+}
+
+/// Formats each bandwidth with [`format_bandwidth`], right-aligned to the
+/// width of the longest one
+///
+/// This is handy for printing a column of values in a table.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_many_bandwidths_aligned;
+///
+/// # #[cfg(not(feature = "display-integer"))]
+/// assert_eq!(
+///     format_many_bandwidths_aligned(&[Bandwidth::new(0, 1), Bandwidth::new(9420, 0)]),
+///     ["    1bps", "9.42Tbps"]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn format_many_bandwidths_aligned(vals: &[Bandwidth]) -> Vec<String> {
+    let formatted: Vec<String> = vals
+        .iter()
+        .map(|val| format_bandwidth(*val).to_string())
+        .collect();
+    let width = formatted.iter().map(|s| s.len()).max().unwrap_or(0);
+    formatted
+        .into_iter()
+        .map(|s| format!("{:>width$}", s, width = width))
+        .collect()
+}
+
+/// Formats bandwidth like [`format_bandwidth`], or as `unlimited` if it
+/// equals `sentinel`
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth_or_unlimited;
+///
+/// let val = format_bandwidth_or_unlimited(Bandwidth::MAX, Bandwidth::MAX);
+/// assert_eq!(val.to_string(), "unlimited");
+/// let val = format_bandwidth_or_unlimited(Bandwidth::new(1, 0), Bandwidth::MAX);
+/// assert_eq!(val.to_string(), "1Gbps");
+/// ```
+pub fn format_bandwidth_or_unlimited(bw: Bandwidth, sentinel: Bandwidth) -> impl fmt::Display {
+    struct OrUnlimited(Option<FormattedBandwidth>);
+
+    impl fmt::Display for OrUnlimited {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match &self.0 {
+                Some(val) => val.fmt(f),
+                None => f.write_str("unlimited"),
+            }
+        }
+    }
+
+    OrUnlimited(if bw == sentinel {
+        None
+    } else {
+        Some(format_bandwidth(bw))
+    })
+}
+
+/// Formats the change from `before` to `after` with a sign, e.g. `+12.5Mbps`
+/// or `-340kbps`
+///
+/// The magnitude is formatted like [`format_bandwidth`], and no change is
+/// `0bps`, without a sign.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth_diff;
+///
+/// let diff = format_bandwidth_diff(Bandwidth::from_mbps(100), Bandwidth::from_mbps(600));
+/// assert_eq!(diff.to_string(), "+500Mbps");
+/// assert!(diff.is_increase());
+/// let diff = format_bandwidth_diff(Bandwidth::from_mbps(1), Bandwidth::from_kbps(660));
+/// assert_eq!(diff.to_string(), "-340kbps");
+/// assert_eq!(diff.magnitude(), Bandwidth::from_kbps(340));
+/// ```
+pub fn format_bandwidth_diff(before: Bandwidth, after: Bandwidth) -> FormattedBandwidthDiff {
+    let (magnitude, increase) = match after.checked_sub(before) {
+        Some(magnitude) => (magnitude, !magnitude.is_zero()),
+        None => (before - after, false),
+    };
+    FormattedBandwidthDiff {
+        magnitude: format_bandwidth(magnitude),
+        increase,
+    }
+}
+
+/// Formats bandwidth into a human-readable string
+///
+/// Note: this format is guaranteed to have same value when using
+/// parse_bandwidth, but we can change some details of the exact composition
+/// of the value.
+///
+/// By default it will format the value with the largest possible unit in decimal form.
+/// If you want to display integer values only, enable the `display-integer` feature.
+/// To choose the form at runtime, use [`format_bandwidth_with_options`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth;
+///
+/// // Enabling the `display-integer` feature will display integer values only
+/// # #[cfg(feature = "display-integer")]
+/// # {
+/// let val1 = Bandwidth::new(9420, 0);
+/// assert_eq!(format_bandwidth(val1).to_string(), "9Tbps 420Gbps");
+/// let val2 = Bandwidth::new(0, 32_000_000);
+/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
+/// # }
+///
+/// // Disabling the `display-integer` feature will display decimal values
+/// # #[cfg(not(feature = "display-integer"))]
+/// # {
+/// let val1 = Bandwidth::new(9420, 0);
+/// assert_eq!(format_bandwidth(val1).to_string(), "9.42Tbps");
+/// let val2 = Bandwidth::new(0, 32_000_000);
+/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
+/// # }
+/// ```
+pub fn format_bandwidth(val: Bandwidth) -> FormattedBandwidth {
+    format_bandwidth_with_options(val, &FormatOptions::default())
+}
+
+/// Formats bandwidth into a human-readable string, with custom options
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_bandwidth_with_options, FormatMode, FormatOptions};
+///
+/// let val = Bandwidth::new(9420, 0);
+/// let opts = FormatOptions::new().mode(FormatMode::Integer);
+/// assert_eq!(format_bandwidth_with_options(val, &opts).to_string(), "9Tbps 420Gbps");
+/// let opts = FormatOptions::new().mode(FormatMode::Decimal).precision(1);
+/// assert_eq!(format_bandwidth_with_options(val, &opts).to_string(), "9.4Tbps");
+/// ```
+pub fn format_bandwidth_with_options(val: Bandwidth, opts: &FormatOptions) -> FormattedBandwidth {
+    FormattedBandwidth {
+        bw: val,
+        opts: *opts,
+    }
+}
+
+/// Formats bandwidth like speed test results, e.g. `150.23 Mbps`
+///
+/// The value always has two fractional digits and is separated from its
+/// unit by a space, independent of the `display-integer` feature. The output
+/// can be read back with [`parse_bandwidth`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth_speedtest;
+///
+/// let val = Bandwidth::new(0, 150_230_000);
+/// assert_eq!(format_bandwidth_speedtest(val).to_string(), "150.23 Mbps");
+/// ```
+pub fn format_bandwidth_speedtest(val: Bandwidth) -> FormattedBandwidth {
+    format_bandwidth_with_options(val, &FormatOptions::decimal().precision(2).separator(" "))
+}
+
+/// Formats bandwidth like [`format_bandwidth_speedtest`], but always in
+/// bps, e.g. `1500.00 bps`
+pub fn format_bandwidth_speedtest_bps(val: Bandwidth) -> FormattedBandwidth {
+    format_bandwidth_with_options(
+        val,
+        &FormatOptions::decimal()
+            .unit(BandwidthUnit::Bps)
+            .precision(2)
+            .separator(" "),
+    )
+}
+
+/// Formats bandwidth in the given form, whatever the `display-integer`
+/// feature says
+///
+/// This is a shortcut for [`FormatOptions::mode`]. It lets a library pass
+/// the choice of form on to its callers at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_bandwidth_styled, FormatMode};
+///
+/// let val = Bandwidth::new(1, 500_000_000);
+/// assert_eq!(format_bandwidth_styled(val, FormatMode::Decimal).to_string(), "1.5Gbps");
+/// assert_eq!(format_bandwidth_styled(val, FormatMode::Integer).to_string(), "1Gbps 500Mbps");
+/// ```
+pub fn format_bandwidth_styled(val: Bandwidth, mode: FormatMode) -> FormattedBandwidth {
+    format_bandwidth_with_options(val, &FormatOptions::new().mode(mode))
+}
+
+/// Formats bandwidth in a fixed unit, regardless of its magnitude
+///
+/// This is a shortcut for [`FormatOptions::unit`], e.g. to keep a column of
+/// values in Mbps.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_bandwidth_with_unit, BandwidthUnit};
+///
+/// # #[cfg(not(feature = "display-integer"))]
+/// # {
+/// let val = format_bandwidth_with_unit(Bandwidth::new(1, 500_000_000), BandwidthUnit::Mbps);
+/// assert_eq!(val.to_string(), "1500Mbps");
+/// let val = format_bandwidth_with_unit(Bandwidth::from_kbps(250), BandwidthUnit::Mbps);
+/// assert_eq!(val.to_string(), "0.25Mbps");
+/// # }
+/// ```
+pub fn format_bandwidth_with_unit(val: Bandwidth, unit: BandwidthUnit) -> FormattedBandwidth {
+    format_bandwidth_with_options(val, &FormatOptions::new().unit(unit))
+}
+
+/// Formats bandwidth in a short form for narrow columns, e.g. `1.5G`
+///
+/// See [`FormattedBandwidth::fmt_compact`]. The output can be read back with
+/// [`parse_bandwidth_compact`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth_compact;
+///
+/// let val = format_bandwidth_compact(Bandwidth::new(1, 500_000_000));
+/// assert_eq!(val.to_string(), "1.5G");
+/// let val = format_bandwidth_compact(Bandwidth::from_bps(123_456));
+/// assert_eq!(val.to_string(), "123K");
+/// ```
+pub fn format_bandwidth_compact(val: Bandwidth) -> FormattedBandwidth {
+    format_bandwidth_styled(val, FormatMode::Compact)
+}
+
+/// Formats bandwidth in decimal form for a column of `width` characters
+///
+/// See [`FormattedBandwidth::fmt_aligned`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth_aligned;
+///
+/// assert_eq!(format_bandwidth_aligned(Bandwidth::from_bps(15), 10).to_string(), "    15bps ");
+/// assert_eq!(format_bandwidth_aligned(Bandwidth::from_gbps(2), 10).to_string(), "     2Gbps");
+/// ```
+pub fn format_bandwidth_aligned(val: Bandwidth, width: usize) -> impl fmt::Display {
+    struct Aligned(FormattedBandwidth, usize);
+
+    impl fmt::Display for Aligned {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt_aligned(f, self.1)
+        }
+    }
+
+    Aligned(format_bandwidth(val), width)
+}
+
+/// Formats bandwidth into a stack-allocated string
+///
+/// The output is the same as `format_bandwidth(val).to_string()`, without
+/// any heap allocation. Only available with the `arrayvec` feature.
+///
+/// In the default decimal format, every value up to roughly 10<sup>15</sup>
+/// Gbps fits in 32 bytes. The integer format of the `display-integer`
+/// feature is longer, e.g. `1Tbps 999Gbps 999Mbps 999kbps 999bps` does not
+/// fit. Output that does not fit is truncated and ends with `…`.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth_array;
+///
+/// let val = Bandwidth::new(0, 32_000_000);
+/// assert_eq!(format_bandwidth_array(val).as_str(), "32Mbps");
+/// ```
+#[cfg(feature = "arrayvec")]
+pub fn format_bandwidth_array(val: Bandwidth) -> arrayvec::ArrayString<32> {
+    use fmt::Write;
+
+    struct Truncating<'a>(&'a mut arrayvec::ArrayString<32>);
+
+    impl fmt::Write for Truncating<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for c in s.chars() {
+                self.0.try_push(c).map_err(|_| fmt::Error)?;
+            }
+            Ok(())
+        }
+    }
+
+    let mut buf = arrayvec::ArrayString::new();
+    if write!(Truncating(&mut buf), "{}", format_bandwidth(val)).is_err() {
+        while buf.remaining_capacity() < '…'.len_utf8() {
+            buf.pop();
+        }
+        buf.push('…');
+    }
+    buf
+}
+
+fn item(
+    f: &mut fmt::Formatter,
+    started: &mut bool,
+    unit: BandwidthUnit,
+    value: u128,
+    separator: &str,
+) -> fmt::Result {
+    if value > 0 {
+        if *started {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}{}{}", value, separator, unit)?;
+        *started = true;
+    }
+    Ok(())
+}
+
+/// A unit of bandwidth in the decimal system
+///
+/// Displays as the canonical unit name, e.g. `Mbps`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(usize)]
+#[non_exhaustive]
+pub enum BandwidthUnit {
+    /// bits per second
+    Bps = 0,
+    /// kilobits per second
+    Kbps = 1,
+    /// megabits per second
+    Mbps = 2,
+    /// gigabits per second
+    Gbps = 3,
+    /// terabits per second
+    Tbps = 4,
+    /// petabits per second
+    Pbps = 5,
+    /// exabits per second
+    Ebps = 6,
+}
+
+impl BandwidthUnit {
+    const ALL: [BandwidthUnit; 7] = [
+        BandwidthUnit::Bps,
+        BandwidthUnit::Kbps,
+        BandwidthUnit::Mbps,
+        BandwidthUnit::Gbps,
+        BandwidthUnit::Tbps,
+        BandwidthUnit::Pbps,
+        BandwidthUnit::Ebps,
+    ];
+
+    /// Number of bps in one of this unit
+    fn scale(self) -> u128 {
+        1000u128.pow(self as u32)
+    }
+
+    /// Returns the number of bits per second in one of this unit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use human_bandwidth::BandwidthUnit;
+    ///
+    /// assert_eq!(BandwidthUnit::Bps.multiplier_bps(), 1);
+    /// assert_eq!(BandwidthUnit::Mbps.multiplier_bps(), 1_000_000);
+    /// ```
+    pub fn multiplier_bps(self) -> u64 {
+        1000u64.pow(self as u32)
+    }
+
+    /// Returns every unit spelling accepted by the parser, the bit rate units
+    /// grouped by unit with the canonical one first, then the byte rate units
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use human_bandwidth::BandwidthUnit;
+    ///
+    /// for alias in BandwidthUnit::all_aliases() {
+    ///     assert!(alias.parse::<BandwidthUnit>().is_ok());
+    /// }
+    /// ```
+    pub fn all_aliases() -> &'static [&'static str] {
+        &[
+            "bps", "bit/s", "b/s", "kbps", "Kbps", "kbit/s", "Kbit/s", "kb/s", "Kb/s", "Mbps",
+            "mbps", "Mbit/s", "mbit/s", "Mb/s", "mb/s", "Gbps", "gbps", "Gbit/s", "gbit/s", "Gb/s",
+            "gb/s", "Tbps", "tbps", "Tbit/s", "tbit/s", "Tb/s", "tb/s", "Pbps", "pbps", "Pbit/s",
+            "pbit/s", "Pb/s", "pb/s", "Ebps", "ebps", "Ebit/s", "ebit/s", "Eb/s", "eb/s", "Bps",
+            "B/s", "kBps", "KBps", "kB/s", "KB/s", "MBps", "MB/s", "GBps", "GB/s", "TBps", "TB/s",
+        ]
+    }
+
+    /// Returns the unit spelled `alias`, see [`BandwidthUnit::all_aliases`]
+    fn from_alias(alias: &str) -> Option<BandwidthUnit> {
+        match alias {
+            "bps" | "bit/s" | "b/s" => Some(BandwidthUnit::Bps),
+            "kbps" | "Kbps" | "kbit/s" | "Kbit/s" | "kb/s" | "Kb/s" => Some(BandwidthUnit::Kbps),
+            "Mbps" | "mbps" | "Mbit/s" | "mbit/s" | "Mb/s" | "mb/s" => Some(BandwidthUnit::Mbps),
+            "Gbps" | "gbps" | "Gbit/s" | "gbit/s" | "Gb/s" | "gb/s" => Some(BandwidthUnit::Gbps),
+            "Tbps" | "tbps" | "Tbit/s" | "tbit/s" | "Tb/s" | "tb/s" => Some(BandwidthUnit::Tbps),
+            "Pbps" | "pbps" | "Pbit/s" | "pbit/s" | "Pb/s" | "pb/s" => Some(BandwidthUnit::Pbps),
+            "Ebps" | "ebps" | "Ebit/s" | "ebit/s" | "Eb/s" | "eb/s" => Some(BandwidthUnit::Ebps),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BandwidthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BandwidthUnit::Bps => f.write_str("bps"),
+            BandwidthUnit::Kbps => f.write_str("kbps"),
+            BandwidthUnit::Mbps => f.write_str("Mbps"),
+            BandwidthUnit::Gbps => f.write_str("Gbps"),
+            BandwidthUnit::Tbps => f.write_str("Tbps"),
+            BandwidthUnit::Pbps => f.write_str("Pbps"),
+            BandwidthUnit::Ebps => f.write_str("Ebps"),
+        }
+    }
+}
+
+/// Parses any of the spellings of [`BandwidthUnit::all_aliases`]
+///
+/// The same units as in [`parse_bandwidth`] are accepted. A byte rate
+/// spelling gives the unit of its prefix, like [`ParsedSpan::unit`]: `MBps`
+/// is [`BandwidthUnit::Mbps`], the factor of 8 is not part of the unit.
+/// Anything else, including surrounding whitespace, fails with
+/// [`Error::UnknownUnitName`].
+///
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::BandwidthUnit;
+///
+/// assert_eq!("Mbit/s".parse(), Ok(BandwidthUnit::Mbps));
+/// assert_eq!("kb/s".parse(), Ok(BandwidthUnit::Kbps));
+/// assert_eq!("MBps".parse(), Ok(BandwidthUnit::Mbps));
+/// assert!("Mibps".parse::<BandwidthUnit>().is_err());
+/// ```
+impl FromStr for BandwidthUnit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<BandwidthUnit, Error> {
+        if s.is_empty() {
+            return Err(Error::Empty);
+        }
+        BandwidthUnit::from_alias(s)
+            .or_else(|| byte_unit_digits(s).map(|digits| BandwidthUnit::ALL[digits as usize / 3]))
+            .ok_or_else(|| Error::UnknownUnitName { unit: s.into() })
+    }
+}
+
+impl FormattedBandwidth {
+    /// Returns a reference to the [`Bandwidth`][] that is being formatted.
+    pub fn get_ref(&self) -> &Bandwidth {
+        &self.bw
+    }
+
+    /// Returns the highest unit with a non-zero contribution
+    ///
+    /// This is the unit used by the decimal format. Zero bandwidth returns
+    /// [`BandwidthUnit::Bps`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth, BandwidthUnit};
+    ///
+    /// let val = format_bandwidth(Bandwidth::new(9420, 5));
+    /// assert_eq!(val.significant_unit(), BandwidthUnit::Tbps);
+    /// let val = format_bandwidth(Bandwidth::new(0, 500));
+    /// assert_eq!(val.significant_unit(), BandwidthUnit::Bps);
+    /// ```
+    pub fn significant_unit(&self) -> BandwidthUnit {
+        let gbps = self.bw.as_gbps();
+        let bps = self.bw.subgbps_bps();
+        if gbps >= 1_000_000_000 {
+            BandwidthUnit::Ebps
+        } else if gbps >= 1_000_000 {
+            BandwidthUnit::Pbps
+        } else if gbps >= 1_000 {
+            BandwidthUnit::Tbps
+        } else if gbps > 0 {
+            BandwidthUnit::Gbps
+        } else if bps >= 1_000_000 {
+            BandwidthUnit::Mbps
+        } else if bps >= 1_000 {
+            BandwidthUnit::Kbps
+        } else {
+            BandwidthUnit::Bps
+        }
+    }
+
+    /// Returns the bandwidth expressed in the given unit as a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth, BandwidthUnit};
+    ///
+    /// let val = format_bandwidth(Bandwidth::new(1, 500_000_000));
+    /// assert_eq!(val.value_in_unit(BandwidthUnit::Gbps), 1.5);
+    /// assert_eq!(val.value_in_unit(BandwidthUnit::Mbps), 1500.0);
+    /// ```
+    pub fn value_in_unit(&self, unit: BandwidthUnit) -> f64 {
+        self.bw.as_bps() as f64 / unit.scale() as f64
+    }
+
+    /// Displays `prefix` followed by the formatted bandwidth
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::format_bandwidth;
+    ///
+    /// let val = format_bandwidth(Bandwidth::new(1, 0)).with_prefix("rate: ");
+    /// assert_eq!(val.to_string(), "rate: 1Gbps");
+    /// ```
+    pub fn with_prefix<P: fmt::Display>(self, prefix: P) -> impl fmt::Display {
+        Affixed(prefix, self, "")
+    }
+
+    /// Displays the formatted bandwidth followed by `suffix`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::format_bandwidth;
+    ///
+    /// let val = format_bandwidth(Bandwidth::new(1, 0)).with_suffix(" (");
+    /// assert_eq!(val.to_string(), "1Gbps (");
+    /// ```
+    pub fn with_suffix<S: fmt::Display>(self, suffix: S) -> impl fmt::Display {
+        Affixed("", self, suffix)
+    }
+
+    /// Displays the bandwidth as a packet rate, assuming packets of `size`
+    /// bytes
+    ///
+    /// The largest of `pps`, `kpps` and `Mpps` is used, with up to three
+    /// fractional digits, further digits are truncated. Rates below one
+    /// packet per second keep their fraction, like `0.008pps`. This is the
+    /// counterpart of
+    /// [`ParseOptions::pps_packet_size_bytes`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::format_bandwidth;
+    ///
+    /// let val = format_bandwidth(Bandwidth::from_gbps(12)).with_packet_size(1500);
+    /// assert_eq!(val.to_string(), "1Mpps");
+    /// let val = format_bandwidth(Bandwidth::from_mbps(18)).with_packet_size(1500);
+    /// assert_eq!(val.to_string(), "1.5kpps");
+    /// ```
+    pub fn with_packet_size(self, size: u16) -> impl fmt::Display {
+        assert!(size > 0, "packet size must be non-zero");
+        PacketRate(self.bw.as_bps() * 1000 / (size as u128 * 8))
+    }
+
+    /// Transforms the wrapped bandwidth, keeping the formatting options
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::format_bandwidth;
+    ///
+    /// let overhead = Bandwidth::from_mbps(500);
+    /// let val = format_bandwidth(Bandwidth::new(1, 0)).map(|bw| bw.saturating_add(overhead));
+    /// assert_eq!(*val.get_ref(), Bandwidth::new(1, 500_000_000));
+    /// ```
+    pub fn map<F: FnOnce(Bandwidth) -> Bandwidth>(self, f: F) -> FormattedBandwidth {
+        FormattedBandwidth {
+            bw: f(self.bw),
+            opts: self.opts,
+        }
+    }
+
+    /// Transforms the wrapped bandwidth with a fallible function, keeping
+    /// the formatting options
+    ///
+    /// Returns `None` if `f` does.
+    pub fn try_map<F: FnOnce(Bandwidth) -> Option<Bandwidth>>(
+        self,
+        f: F,
+    ) -> Option<FormattedBandwidth> {
+        Some(FormattedBandwidth {
+            bw: f(self.bw)?,
+            opts: self.opts,
+        })
+    }
+
+    /// Sets the number of fractional digits of the decimal form
+    ///
+    /// This is the same as [`FormatOptions::precision`]: the value is rounded
+    /// half up, or padded with zeros. The integer form, used with the
+    /// `display-integer` feature, is not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::format_bandwidth;
+    ///
+    /// # #[cfg(not(feature = "display-integer"))]
+    /// # {
+    /// let val = format_bandwidth(Bandwidth::new(1, 500_000_000)).precision(3);
+    /// assert_eq!(val.to_string(), "1.500Gbps");
+    /// # }
+    /// ```
+    pub fn precision(mut self, digits: usize) -> FormattedBandwidth {
+        self.opts = self.opts.precision(digits);
+        self
+    }
+
+    /// Enabling the `display-integer` feature will display integer values only
+    ///
+    /// This method is preserved for backward compatibility and custom formatting.
+    pub fn fmt_integer(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_options(f, &self.opts.mode(FormatMode::Integer))
+    }
+
+    /// Disabling the `display-integer` feature will display decimal values
+    ///
+    /// This method is preserved for custom formatting. The precision of `f`,
+    /// if any, sets the number of fractional digits as in
+    /// [`FormatOptions::precision`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth_with_options, FormatOptions};
+    ///
+    /// let val = format_bandwidth_with_options(Bandwidth::new(1, 500_000_000), &FormatOptions::decimal());
+    /// assert_eq!(format!("{}", val), "1.5Gbps");
+    /// assert_eq!(format!("{:.2}", val), "1.50Gbps");
+    /// assert_eq!(format!("{:.0}", val), "2Gbps");
+    /// ```
+    pub fn fmt_decimal(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_options(f, &self.opts.mode(FormatMode::Decimal))
+    }
+
+    /// Formats with `opts` instead of the options this value was created with
+    ///
+    /// This is useful in generic code that receives the options from an
+    /// outer context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth, FormatOptions, FormattedBandwidth};
+    /// use std::fmt;
+    ///
+    /// struct Integer(FormattedBandwidth);
+    ///
+    /// impl fmt::Display for Integer {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         self.0.fmt_with_options(f, &FormatOptions::integer())
+    ///     }
+    /// }
+    ///
+    /// let val = Integer(format_bandwidth(Bandwidth::new(1, 500_000_000)));
+    /// assert_eq!(val.to_string(), "1Gbps 500Mbps");
+    /// ```
+    pub fn fmt_with_options(&self, f: &mut fmt::Formatter, opts: &FormatOptions) -> fmt::Result {
+        match opts.mode {
+            FormatMode::Decimal => self.write_decimal(f, opts),
+            FormatMode::Integer => self.write_integer(f, opts),
+            FormatMode::Auto => {
+                let unit = opts.unit.unwrap_or_else(|| self.significant_unit());
+                if self.bw.as_bps() % unit.scale() == 0 {
+                    self.write_integer(f, &opts.unit(unit))
+                } else {
+                    self.write_decimal(f, opts)
+                }
+            }
+            FormatMode::Compact => self.write_compact(f),
+        }
+    }
+
+    /// Formats with at most three significant digits and a bare prefix, e.g.
+    /// `1.5G`
+    ///
+    /// The prefixes are `K`, `M`, `G`, `T`, `P` and `E`, and bps have none:
+    /// `B` would read as bytes. The value is rounded half up, so it is within
+    /// 0.5% of the bandwidth. Only values beyond 999Ebps have more digits;
+    /// near [`Bandwidth::MAX`] they are truncated rather than rounded up, so
+    /// the output still parses back.
+    /// The unit, precision and separator of the options are ignored.
+    ///
+    /// This is [`FormatMode::Compact`], see [`format_bandwidth_compact`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth, FormattedBandwidth};
+    /// use std::fmt;
+    ///
+    /// struct Compact(FormattedBandwidth);
+    ///
+    /// impl fmt::Display for Compact {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         self.0.fmt_compact(f)
+    ///     }
+    /// }
+    ///
+    /// let val = Compact(format_bandwidth(Bandwidth::from_kbps(99_960)));
+    /// assert_eq!(val.to_string(), "100M");
+    /// let val = Compact(format_bandwidth(Bandwidth::from_bps(999)));
+    /// assert_eq!(val.to_string(), "999");
+    /// ```
+    pub fn fmt_compact(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_options(f, &self.opts.mode(FormatMode::Compact))
+    }
+
+    /// Formats exact multiples of the unit in integer form, other values in
+    /// decimal form
+    ///
+    /// This is [`FormatMode::Auto`]: with a precision set, `1Gbps` is not
+    /// padded to `1.000Gbps`, and `1.5Gbps` is never split into
+    /// `1Gbps 500Mbps`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth_with_options, FormatMode, FormatOptions};
+    ///
+    /// let opts = FormatOptions::new().mode(FormatMode::Auto).precision(2);
+    /// let val = format_bandwidth_with_options(Bandwidth::new(1, 0), &opts);
+    /// assert_eq!(val.to_string(), "1Gbps");
+    /// let val = format_bandwidth_with_options(Bandwidth::new(1, 500_000_000), &opts);
+    /// assert_eq!(val.to_string(), "1.50Gbps");
+    /// ```
+    pub fn fmt_adaptive(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_options(f, &self.opts.mode(FormatMode::Auto))
+    }
+
+    fn write_integer(&self, f: &mut fmt::Formatter, opts: &FormatOptions) -> fmt::Result {
+        let bps = self.bw.as_bps();
+        let separator = opts.separator;
+        let top = opts.unit.unwrap_or(BandwidthUnit::Ebps);
+
+        if bps == 0 {
+            return write!(
+                f,
+                "0{}{}",
+                separator,
+                opts.unit.unwrap_or(BandwidthUnit::Bps)
+            );
+        }
+
+        let started = &mut false;
+        item(f, started, top, bps / top.scale(), separator)?;
+        for unit in BandwidthUnit::ALL.iter().rev().filter(|u| **u < top) {
+            item(f, started, *unit, bps / unit.scale() % 1000, separator)?;
+        }
+        Ok(())
+    }
+
+    /// Formats in decimal form, padded to `width` characters so that the
+    /// numbers of a column are right-aligned and their units left-aligned
+    ///
+    /// The unit is padded to 4 characters, so `bps` gets a trailing space and
+    /// the values of the same unit line up on their last digit. The leading
+    /// padding uses the fill of `f` (default: a space). With a fixed unit,
+    /// see [`FormatOptions::unit`], the decimal points line up too when a
+    /// precision is set. See also [`format_bandwidth_aligned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{format_bandwidth, FormattedBandwidth};
+    /// use std::fmt;
+    ///
+    /// struct Aligned(FormattedBandwidth);
+    ///
+    /// impl fmt::Display for Aligned {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         self.0.fmt_aligned(f, 10)
+    ///     }
+    /// }
+    ///
+    /// let val = Aligned(format_bandwidth(Bandwidth::from_bps(15)));
+    /// assert_eq!(val.to_string(), "    15bps ");
+    /// let val = Aligned(format_bandwidth(Bandwidth::from_mbps(150)));
+    /// assert_eq!(val.to_string(), "   150Mbps");
+    /// ```
+    pub fn fmt_aligned(&self, f: &mut fmt::Formatter, width: usize) -> fmt::Result {
+        let unit = self.opts.unit.unwrap_or_else(|| self.significant_unit());
+        let body = self.unpadded(f, self.opts.mode(FormatMode::Decimal).unit(unit));
+        let post = 4 - char_count(&unit);
+        fill(f, f.fill(), width.saturating_sub(char_count(&body) + post))?;
+        write!(f, "{}", body)?;
+        fill(f, ' ', post)
+    }
+
+    fn unpadded(&self, f: &fmt::Formatter, opts: FormatOptions) -> Unpadded<'_> {
+        Unpadded {
+            val: self,
+            opts: match f.precision() {
+                Some(precision) => opts.precision(precision),
+                None => opts,
+            },
+            sign: if f.sign_plus() { Some('+') } else { None },
+        }
+    }
+
+    fn write_compact(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bps = self.bw.as_bps();
+        let mut unit = self.significant_unit();
+        loop {
+            let scale = unit.scale();
+            let digits = match bps / scale {
+                0..=9 => 2,
+                10..=99 => 1,
+                _ => 0,
+            };
+            let pow = 10u128.pow(digits);
+            let mut val = (bps * pow + scale / 2) / scale;
+            if unit == BandwidthUnit::Ebps && val * (scale / pow) > Bandwidth::MAX.as_bps() {
+                // rounding up would not parse back, truncate instead
+                val = bps * pow / scale;
+            }
+            if val >= 1000 * pow && unit < BandwidthUnit::Ebps {
+                // rounded up to 1000, e.g. 999.6M is 1G
+                unit = BandwidthUnit::ALL[unit as usize + 1];
+                continue;
+            }
+            write!(f, "{}", val / pow)?;
+            let (mut fraction, mut width) = (val % pow, digits as usize);
+            if fraction > 0 {
+                while fraction % 10 == 0 {
+                    fraction /= 10;
+                    width -= 1;
+                }
+                write!(f, ".{:0width$}", fraction, width = width)?;
+            }
+            return match unit {
+                BandwidthUnit::Bps => Ok(()),
+                BandwidthUnit::Kbps => f.write_str("K"),
+                BandwidthUnit::Mbps => f.write_str("M"),
+                BandwidthUnit::Gbps => f.write_str("G"),
+                BandwidthUnit::Tbps => f.write_str("T"),
+                BandwidthUnit::Pbps => f.write_str("P"),
+                BandwidthUnit::Ebps => f.write_str("E"),
+            };
+        }
+    }
+
+    fn write_decimal(&self, f: &mut fmt::Formatter, opts: &FormatOptions) -> fmt::Result {
+        let mut unit = opts.unit.unwrap_or_else(|| self.significant_unit());
+        let bps = self.bw.as_bps();
+
+        match f.precision().or(opts.precision) {
+            Some(precision) => {
+                let (int, fraction, digits) = loop {
+                    let scale = unit.scale();
+                    let mut int = bps / scale;
+                    let mut fraction = bps % scale;
+                    let mut digits = 3 * unit as usize;
+                    if precision < digits {
+                        let div = 10u128.pow((digits - precision) as u32);
+                        fraction = (fraction + div / 2) / div;
+                        digits = precision;
+                        if fraction == 10u128.pow(digits as u32) {
+                            int += 1;
+                            fraction = 0;
+                        }
+                    }
+                    // rounding up may reach the next unit, `999.6Mbps` is `1Gbps`
+                    if opts.unit.is_none() && int >= 1000 && unit < BandwidthUnit::Ebps {
+                        unit = BandwidthUnit::ALL[unit as usize + 1];
+                        continue;
+                    }
+                    break (int, fraction, digits);
+                };
+                write!(f, "{}", int)?;
+                if precision > 0 {
+                    f.write_str(".")?;
+                    if digits > 0 {
+                        write!(f, "{:0width$}", fraction, width = digits)?;
+                    }
+                    for _ in digits..precision {
+                        f.write_str("0")?;
+                    }
+                }
+            }
+            None => {
+                let scale = unit.scale();
+                let int = bps / scale;
+                let mut fraction = bps % scale;
+                let mut digits = 3 * unit as usize;
+                write!(f, "{}", int)?;
+                if fraction > 0 {
+                    while fraction % 10 == 0 {
+                        fraction /= 10;
+                        digits -= 1;
+                    }
+                    write!(f, ".{:0width$}", fraction, width = digits)?;
+                }
+            }
+        }
+        write!(f, "{}{}", opts.separator, unit)
+    }
+}
+
+/// Displays the bandwidth in decimal form, or in integer form with the
+/// `display-integer` feature
+///
+/// Bandwidth is never negative, so the `+` flag (`{:+}`) simply prepends a
+/// `+` sign.
+/// The width and fill of the formatter are respected, right-aligning by
+/// default: `format!("{:>15}", format_bandwidth(val))` pads the value to 15
+/// characters. See also [`FormattedBandwidth::fmt_aligned`].
+impl fmt::Display for FormattedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let body = self.unpadded(f, self.opts);
+        pad(f, &body)
+    }
+}
+
+/// A [`FormattedBandwidth`] with the sign and precision of a formatter, but
+/// without its padding
+struct Unpadded<'a> {
+    val: &'a FormattedBandwidth,
+    opts: FormatOptions,
+    sign: Option<char>,
+}
+
+impl fmt::Display for Unpadded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(sign) = self.sign {
+            fmt::Write::write_char(f, sign)?;
+        }
+        self.val.fmt_with_options(f, &self.opts)
+    }
+}
+
+/// Writes `body` padded to the width of `f`, right-aligned by default
+pub(crate) fn pad<T: fmt::Display>(f: &mut fmt::Formatter, body: &T) -> fmt::Result {
+    let width = match f.width() {
+        Some(width) => width,
+        None => return write!(f, "{}", body),
+    };
+    let pad = width.saturating_sub(char_count(body));
+    let (pre, post) = match f.align().unwrap_or(fmt::Alignment::Right) {
+        fmt::Alignment::Left => (0, pad),
+        fmt::Alignment::Right => (pad, 0),
+        fmt::Alignment::Center => (pad / 2, pad - pad / 2),
+    };
+    fill(f, f.fill(), pre)?;
+    write!(f, "{}", body)?;
+    fill(f, f.fill(), post)
+}
+
+/// Counts the chars written by `val`
+fn char_count<T: fmt::Display>(val: &T) -> usize {
+    struct Counter(usize);
+
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.chars().count();
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    // writing to the counter cannot fail
+    let _ = fmt::write(&mut counter, format_args!("{}", val));
+    counter.0
+}
+
+fn fill(f: &mut fmt::Formatter, c: char, n: usize) -> fmt::Result {
+    for _ in 0..n {
+        fmt::Write::write_char(f, c)?;
+    }
+    Ok(())
+}
+
+/// A wrapper type that allows you to Display the signed difference of two
+/// bandwidths
+///
+/// Returned by [`format_bandwidth_diff`]. Like [`FormattedBandwidth`], it
+/// respects the width, fill and precision of the formatter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormattedBandwidthDiff {
+    magnitude: FormattedBandwidth,
+    increase: bool,
+}
+
+impl FormattedBandwidthDiff {
+    /// Returns `true` if the bandwidth grew
+    pub fn is_increase(&self) -> bool {
+        self.increase
+    }
+
+    /// Returns `true` if the bandwidth did not change
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.bw.is_zero()
+    }
+
+    /// Returns the absolute difference
+    pub fn magnitude(&self) -> Bandwidth {
+        self.magnitude.bw
+    }
+}
+
+impl fmt::Display for FormattedBandwidthDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut body = self.magnitude.unpadded(f, self.magnitude.opts);
+        body.sign = if self.is_zero() {
+            None
+        } else if self.increase {
+            Some('+')
+        } else {
+            Some('-')
+        };
+        pad(f, &body)
+    }
+}
+
+/// A packet rate in thousandths of a packet per second
+struct PacketRate(u128);
+
+impl fmt::Display for PacketRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (scale, unit) = if self.0 >= 1_000_000_000 {
+            (1_000_000_000, "Mpps")
+        } else if self.0 >= 1_000_000 {
+            (1_000_000, "kpps")
+        } else {
+            (1_000, "pps")
+        };
+        write!(f, "{}", self.0 / scale)?;
+        let milli = self.0 % scale * 1000 / scale;
+        if milli > 0 {
+            let (mut milli, mut width) = (milli, 3);
+            while milli % 10 == 0 {
+                milli /= 10;
+                width -= 1;
+            }
+            write!(f, ".{:0width$}", milli, width = width)?;
+        }
+        f.write_str(unit)
+    }
+}
+
+struct Affixed<P, S>(P, FormattedBandwidth, S);
+
+impl<P: fmt::Display, S: fmt::Display> fmt::Display for Affixed<P, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{}", self.0, self.1, self.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bandwidth::Bandwidth;
+
+    #[test]
+    fn test_units() {
+        assert_eq!(parse_bandwidth("1bps"), Ok(Bandwidth::new(0, 1)));
+        assert_eq!(parse_bandwidth("2bit/s"), Ok(Bandwidth::new(0, 2)));
+        assert_eq!(parse_bandwidth("15b/s"), Ok(Bandwidth::new(0, 15)));
+        assert_eq!(parse_bandwidth("51kbps"), Ok(Bandwidth::new(0, 51_000)));
+        assert_eq!(parse_bandwidth("79Kbps"), Ok(Bandwidth::new(0, 79_000)));
+        assert_eq!(parse_bandwidth("81kbit/s"), Ok(Bandwidth::new(0, 81_000)));
+        assert_eq!(parse_bandwidth("100Kbit/s"), Ok(Bandwidth::new(0, 100_000)));
+        assert_eq!(parse_bandwidth("150kb/s"), Ok(Bandwidth::new(0, 150_000)));
+        assert_eq!(parse_bandwidth("410Kb/s"), Ok(Bandwidth::new(0, 410_000)));
+        assert_eq!(parse_bandwidth("12Mbps"), Ok(Bandwidth::new(0, 12_000_000)));
+        assert_eq!(parse_bandwidth("16mbps"), Ok(Bandwidth::new(0, 16_000_000)));
+        assert_eq!(
+            parse_bandwidth("24Mbit/s"),
+            Ok(Bandwidth::new(0, 24_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("36mbit/s"),
+            Ok(Bandwidth::new(0, 36_000_000))
+        );
+        assert_eq!(parse_bandwidth("48Mb/s"), Ok(Bandwidth::new(0, 48_000_000)));
+        assert_eq!(parse_bandwidth("96mb/s"), Ok(Bandwidth::new(0, 96_000_000)));
+        assert_eq!(parse_bandwidth("2Gbps"), Ok(Bandwidth::new(2, 0)));
+        assert_eq!(parse_bandwidth("4gbps"), Ok(Bandwidth::new(4, 0)));
+        assert_eq!(parse_bandwidth("6Gbit/s"), Ok(Bandwidth::new(6, 0)));
+        assert_eq!(parse_bandwidth("8gbit/s"), Ok(Bandwidth::new(8, 0)));
+        assert_eq!(parse_bandwidth("16Gb/s"), Ok(Bandwidth::new(16, 0)));
+        assert_eq!(parse_bandwidth("40gb/s"), Ok(Bandwidth::new(40, 0)));
+        assert_eq!(parse_bandwidth("1Tbps"), Ok(Bandwidth::new(1_000, 0)));
+        assert_eq!(parse_bandwidth("2tbps"), Ok(Bandwidth::new(2_000, 0)));
+        assert_eq!(parse_bandwidth("4Tbit/s"), Ok(Bandwidth::new(4_000, 0)));
+        assert_eq!(parse_bandwidth("8tbit/s"), Ok(Bandwidth::new(8_000, 0)));
+        assert_eq!(parse_bandwidth("16Tb/s"), Ok(Bandwidth::new(16_000, 0)));
+        assert_eq!(parse_bandwidth("32tb/s"), Ok(Bandwidth::new(32_000, 0)));
+    }
+
+    #[test]
+    fn test_peta_exa() {
+        assert_eq!(parse_bandwidth("1Pbps"), Ok(Bandwidth::new(1_000_000, 0)));
+        assert_eq!(parse_bandwidth("2.5Pbps"), Ok(Bandwidth::new(2_500_000, 0)));
+        assert_eq!(parse_bandwidth("3pbit/s"), Ok(Bandwidth::new(3_000_000, 0)));
+        assert_eq!(parse_bandwidth("4Pb/s"), Ok(Bandwidth::new(4_000_000, 0)));
+        assert_eq!(
+            parse_bandwidth("1Ebps"),
+            Ok(Bandwidth::new(1_000_000_000, 0))
+        );
+        // the 18th digit of `Ebps` is 1bps, beyond the default 12 digits
+        let full = ParseOptions::new().max_fraction_digits(18);
+        assert_eq!(
+            parse_bandwidth("1.000000000000000001Ebps"),
+            Ok(Bandwidth::new(1_000_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth_with_options("1.000000000000000001Ebps", &full),
+            Ok(Bandwidth::new(1_000_000_000, 1))
+        );
+        assert_eq!(
+            parse_bandwidth("2ebit/s"),
+            Ok(Bandwidth::new(2_000_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("1Eb/s 1bps"),
+            Ok(Bandwidth::new(1_000_000_000, 1))
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073Ebps"),
+            Ok(Bandwidth::new(18_446_744_073_000_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("18446744074Ebps"),
+            Err(Error::NumberOverflow)
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073709552Pbps"),
+            Err(Error::NumberOverflow)
+        );
+
+        let decimal = |bw| format_bandwidth_with_options(bw, &FormatOptions::decimal()).to_string();
+        let integer = |bw| format_bandwidth_with_options(bw, &FormatOptions::integer()).to_string();
+        assert_eq!(decimal(Bandwidth::new(2_500_000, 0)), "2.5Pbps");
+        assert_eq!(decimal(Bandwidth::new(1_000_000_000, 0)), "1Ebps");
+        assert_eq!(integer(Bandwidth::new(2_500_000, 0)), "2Pbps 500Tbps");
+        assert_eq!(integer(Bandwidth::new(1_000_000_001, 0)), "1Ebps 1Gbps");
+        for bw in [
+            Bandwidth::new(2_500_000, 0),
+            Bandwidth::new(1_000_000_000, 0),
+            Bandwidth::new(1_234_567_890_123, 456_789_012),
+            Bandwidth::MAX,
+        ] {
+            assert_eq!(parse_bandwidth_with_options(&decimal(bw), &full), Ok(bw));
+            assert_eq!(parse_bandwidth(&integer(bw)), Ok(bw));
+        }
+    }
+
+    #[test]
+    fn test_long_fraction() {
+        // digits past the 12th are dropped, and the unit still starts after
+        // all of them
+        assert_eq!(
+            parse_bandwidth("1.1234567890123Gbps"),
+            Ok(Bandwidth::new(1, 123_456_789))
+        );
+        assert_eq!(
+            parse_bandwidth("1.12345678901234567890123Ebps"),
+            Ok(Bandwidth::new(1_123_456_789, 12_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth_with_options(
+                "1.12345678901234567890123Ebps",
+                &ParseOptions::new().max_fraction_digits(18)
+            ),
+            Ok(Bandwidth::new(1_123_456_789, 12_345_678))
+        );
+        assert_eq!(
+            parse_bandwidth("1.00000000000000000000009Gbit"),
+            Err(Error::UnknownUnit {
+                start: 25,
+                end: 29,
+                unit: "Gbit".into(),
+                value: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(parse_bandwidth("1.5bps"), Ok(Bandwidth::new(0, 1)));
+        assert_eq!(parse_bandwidth("2.5bit/s"), Ok(Bandwidth::new(0, 2)));
+        assert_eq!(parse_bandwidth("15.5b/s"), Ok(Bandwidth::new(0, 15)));
+        assert_eq!(parse_bandwidth("51.6kbps"), Ok(Bandwidth::new(0, 51_600)));
+        assert_eq!(parse_bandwidth("79.78Kbps"), Ok(Bandwidth::new(0, 79_780)));
+        assert_eq!(
+            parse_bandwidth("81.923kbit/s"),
+            Ok(Bandwidth::new(0, 81_923))
+        );
+        assert_eq!(
+            parse_bandwidth("100.1234Kbit/s"),
+            Ok(Bandwidth::new(0, 100_123))
+        );
+        assert_eq!(
+            parse_bandwidth("150.12345kb/s"),
+            Ok(Bandwidth::new(0, 150_123))
+        );
+        assert_eq!(
+            parse_bandwidth("410.123456Kb/s"),
+            Ok(Bandwidth::new(0, 410_123))
+        );
+        assert_eq!(
+            parse_bandwidth("12.123Mbps"),
+            Ok(Bandwidth::new(0, 12_123_000))
+        );
+        assert_eq!(
+            parse_bandwidth("16.1234mbps"),
+            Ok(Bandwidth::new(0, 16_123_400))
+        );
+        assert_eq!(
+            parse_bandwidth("24.12345Mbit/s"),
+            Ok(Bandwidth::new(0, 24_123_450))
+        );
+        assert_eq!(
+            parse_bandwidth("36.123456mbit/s"),
+            Ok(Bandwidth::new(0, 36_123_456))
+        );
+        assert_eq!(
+            parse_bandwidth("48.123Mb/s"),
+            Ok(Bandwidth::new(0, 48_123_000))
+        );
+        assert_eq!(
+            parse_bandwidth("96.1234mb/s"),
+            Ok(Bandwidth::new(0, 96_123_400))
+        );
+        assert_eq!(
+            parse_bandwidth("2.123Gbps"),
+            Ok(Bandwidth::new(2, 123_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("4.1234gbps"),
+            Ok(Bandwidth::new(4, 123_400_000))
+        );
+        assert_eq!(
+            parse_bandwidth("6.12345Gbit/s"),
+            Ok(Bandwidth::new(6, 123_450_000))
+        );
+        assert_eq!(
+            parse_bandwidth("8.123456gbit/s"),
+            Ok(Bandwidth::new(8, 123_456_000))
+        );
+        assert_eq!(
+            parse_bandwidth("16.123456789Gb/s"),
+            Ok(Bandwidth::new(16, 123_456_789))
+        );
+        assert_eq!(
+            parse_bandwidth("40.12345678912gb/s"),
+            Ok(Bandwidth::new(40, 123_456_789))
+        );
+        assert_eq!(parse_bandwidth("1.123Tbps"), Ok(Bandwidth::new(1_123, 0)));
+        assert_eq!(
+            parse_bandwidth("2.1234tbps"),
+            Ok(Bandwidth::new(2_123, 400_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("4.12345Tbit/s"),
+            Ok(Bandwidth::new(4_123, 450_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("8.123456tbit/s"),
+            Ok(Bandwidth::new(8_123, 456_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("16.123456789Tb/s"),
+            Ok(Bandwidth::new(16_123, 456_789_000))
+        );
+        assert_eq!(
+            parse_bandwidth("32.12345678912tb/s"),
+            Ok(Bandwidth::new(32_123, 456_789_120))
+        );
+    }
+
+    #[test]
+    fn test_prefix_case() {
+        assert_eq!(parse_bandwidth("1Kbps"), parse_bandwidth("1kbps"));
+        assert_eq!(parse_bandwidth("1Kbps"), Ok(Bandwidth::from_bps(1000)));
+        assert_eq!(parse_bandwidth("1Kbit/s"), Ok(Bandwidth::from_bps(1000)));
+        assert_eq!(parse_bandwidth("1Kb/s"), Ok(Bandwidth::from_bps(1000)));
+        assert_eq!(parse_bandwidth("1mbps"), parse_bandwidth("1Mbps"));
+        assert_eq!(parse_bandwidth("1gbps"), parse_bandwidth("1Gbps"));
+        assert_eq!(parse_bandwidth("1tbps"), parse_bandwidth("1Tbps"));
+    }
+
+    #[test]
+    fn test_combo() {
+        assert_eq!(
+            parse_bandwidth("1bps 2bit/s 3b/s"),
+            Ok(Bandwidth::new(0, 6))
+        );
+        assert_eq!(
+            parse_bandwidth("4kbps 5Kbps 6kbit/s"),
+            Ok(Bandwidth::new(0, 15_000))
+        );
+        assert_eq!(
+            parse_bandwidth("7Mbps 8mbps 9Mbit/s"),
+            Ok(Bandwidth::new(0, 24_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("10Gbps 11gbps 12Gbit/s"),
+            Ok(Bandwidth::new(33, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("13Tbps 14tbps 15Tbit/s"),
+            Ok(Bandwidth::new(42_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("10Gbps 5Mbps 1b/s"),
+            Ok(Bandwidth::new(10, 5_000_001))
+        );
+        assert_eq!(
+            parse_bandwidth("36Mbps 12kbps 24bps"),
+            Ok(Bandwidth::new(0, 36_012_024))
+        );
+    }
+
+    #[test]
+    fn test_decimal_combo() {
+        assert_eq!(
+            parse_bandwidth("1.1bps 2.2bit/s 3.3b/s"),
+            Ok(Bandwidth::new(0, 6))
+        );
+        assert_eq!(
+            parse_bandwidth("4.4kbps 5.5Kbps 6.6kbit/s"),
+            Ok(Bandwidth::new(0, 16_500))
+        );
+        assert_eq!(
+            parse_bandwidth("7.7Mbps 8.8mbps 9.9Mbit/s"),
+            Ok(Bandwidth::new(0, 26_400_000))
+        );
+        assert_eq!(
+            parse_bandwidth("10.10Gbps 11.11gbps 12.12Gbit/s"),
+            Ok(Bandwidth::new(33, 330_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("13.13Tbps 14.14tbps 15.15Tbit/s"),
+            Ok(Bandwidth::new(42_420, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("10.1Gbps 5.2Mbps 1.3b/s"),
+            Ok(Bandwidth::new(10, 105_200_001))
+        );
+        assert_eq!(
+            parse_bandwidth("36.1Mbps 12.2kbps 24.3bps"),
+            Ok(Bandwidth::new(0, 36_112_224))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_parse_spans() {
+        fn spans(s: &str) -> Result<Vec<(Bandwidth, &str)>, Error> {
+            parse_bandwidth_spans(s).map(|spans| {
+                spans
+                    .into_iter()
+                    .map(|span| (span.bandwidth, &s[span.start..span.end]))
+                    .collect()
+            })
+        }
+
+        assert_eq!(
+            spans("1Gbps 500Mbps"),
+            Ok(vec![
+                (Bandwidth::new(1, 0), "1Gbps"),
+                (Bandwidth::new(0, 500_000_000), "500Mbps"),
+            ])
+        );
+        assert_eq!(
+            spans("  1.5Gbps2kbps 1 000 bps "),
+            Ok(vec![
+                (Bandwidth::new(1, 500_000_000), "1.5Gbps"),
+                (Bandwidth::new(0, 2_000), "2kbps"),
+                (Bandwidth::new(0, 1_000), "1 000 bps"),
+            ])
+        );
+        assert_eq!(
+            spans("2500Mbps"),
+            Ok(vec![(Bandwidth::new(2, 500_000_000), "2500Mbps")])
+        );
+        assert_eq!(
+            parse_bandwidth_spans("1.050Tbps 2MBps"),
+            Ok(vec![
+                ParsedSpan {
+                    value: 1,
+                    fraction: 50,
+                    fraction_digits: 3,
+                    unit: BandwidthUnit::Tbps,
+                    start: 0,
+                    end: 9,
+                    bandwidth: Bandwidth::from_gbps(1050),
+                },
+                ParsedSpan {
+                    value: 2,
+                    fraction: 0,
+                    fraction_digits: 0,
+                    unit: BandwidthUnit::Mbps,
+                    start: 10,
+                    end: 15,
+                    bandwidth: Bandwidth::from_mbps(16),
+                },
+            ])
+        );
+        assert_eq!(parse_bandwidth_spans(""), Err(Error::Empty));
+        assert_eq!(
+            parse_bandwidth_spans("1Gbps 2Gbit"),
+            Err(Error::UnknownUnit {
+                start: 7,
+                end: 11,
+                unit: "Gbit".into(),
+                value: 2,
+            })
+        );
+
+        for s in [
+            "9Tbps 420Gbps",
+            "1.999Mbps 999.999kbps 5bps",
+            "36.1Mbps 12.2kbps",
+        ] {
+            let spans = spans(s).unwrap();
+            let total: Bandwidth = spans.iter().map(|(bw, _)| *bw).sum();
+            assert_eq!(Ok(total), parse_bandwidth(s));
+            for (bw, span) in spans {
+                assert_eq!(parse_bandwidth(span), Ok(bw));
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_parse_cow() {
+        assert_eq!(
+            parse_bandwidth_cow(Cow::Borrowed("1Gbps 500Mbps")),
+            Ok(Bandwidth::new(1, 500_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth_cow(Cow::Owned(format!("{}Mbps", 32))),
+            Ok(Bandwidth::from_mbps(32))
+        );
+        assert_eq!(parse_bandwidth_cow(Cow::Borrowed("")), Err(Error::Empty));
+    }
+
+    #[test]
+    fn test_locale_independent() {
+        // neither parsing nor formatting consults the locale, so these hold
+        // with any LC_NUMERIC, including comma-decimal ones like de_DE
+        assert_eq!(
+            parse_bandwidth("1.5Gbps"),
+            Ok(Bandwidth::new(1, 500_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("150.024kbps"),
+            Ok(Bandwidth::new(0, 150_024))
+        );
+        assert_eq!(parse_bandwidth("1,5Gbps"), Err(Error::InvalidCharacter(1)));
+        assert_eq!(
+            parse_bandwidth("1.000,5Mbps"),
+            Err(Error::InvalidCharacter(5))
+        );
+        let s = format_bandwidth_with_options(
+            Bandwidth::new(1, 500_000_000),
+            &FormatOptions::decimal().precision(2),
+        )
+        .to_string();
+        assert_eq!(s, "1.50Gbps");
+        assert_eq!(parse_bandwidth(&s), Ok(Bandwidth::new(1, 500_000_000)));
+    }
+
+    #[test]
+    fn test_whitespace_in_number() {
+        assert_eq!(parse_bandwidth("1 000 Mbps"), Ok(Bandwidth::new(1, 0)));
+        assert_eq!(parse_bandwidth("1 000Mbps"), Ok(Bandwidth::new(1, 0)));
+        assert_eq!(
+            parse_bandwidth("1 000 000 bps"),
+            Ok(Bandwidth::new(0, 1_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("1 500.5 kbps 2 Gbps"),
+            Ok(Bandwidth::new(2, 1_500_500))
+        );
+        #[cfg(not(feature = "bare-prefix"))]
+        assert_eq!(
+            parse_bandwidth("1 000 M bps"),
+            Err(Error::UnknownUnit {
+                start: 6,
+                end: 7,
+                unit: "M".into(),
+                value: 1000
+            })
+        );
+        // `M` is a unit of its own, so `bps` lacks a number
+        #[cfg(feature = "bare-prefix")]
+        assert_eq!(
+            parse_bandwidth("1 000 M bps"),
+            Err(Error::NumberExpected(8))
+        );
+    }
+
+    #[test]
+    fn test_underscore_in_number() {
+        assert_eq!(parse_bandwidth("1_000kbps"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(
+            parse_bandwidth("1_000_000Mbps"),
+            Ok(Bandwidth::new(1_000, 0))
+        );
+        assert_eq!(parse_bandwidth("1_000_000bps"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(
+            parse_bandwidth("1.000_5Gbps 2_0bps"),
+            Ok(Bandwidth::new(1, 500_020))
+        );
+        assert_eq!(parse_bandwidth("_1Mbps"), Err(Error::NumberExpected(0)));
+        assert_eq!(parse_bandwidth("1_Mbps"), Err(Error::InvalidCharacter(1)));
+        assert_eq!(
+            parse_bandwidth("1__000kbps"),
+            Err(Error::InvalidCharacter(1))
+        );
+        assert_eq!(parse_bandwidth("1_.5Gbps"), Err(Error::InvalidCharacter(1)));
+        assert_eq!(parse_bandwidth("1._5Gbps"), Err(Error::InvalidCharacter(2)));
+        assert_eq!(
+            parse_bandwidth("1 _000kbps"),
+            Err(Error::InvalidCharacter(2))
+        );
+        assert_eq!(
+            parse_bandwidth("1_ 000kbps"),
+            Err(Error::InvalidCharacter(1))
+        );
+        assert_eq!(
+            parse_bandwidth("1Gbps _5Mbps"),
+            Err(Error::NumberExpected(6))
+        );
+        assert_eq!(
+            parse_bandwidth_partial("1_000Mbps 10s"),
+            Ok((Bandwidth::from_gbps(1), " 10s"))
+        );
+    }
+
+    #[test]
+    fn test_format_options() {
+        let fmt = |bw, opts: FormatOptions| format_bandwidth_with_options(bw, &opts).to_string();
+        let decimal = FormatOptions::new().mode(FormatMode::Decimal);
+        let integer = FormatOptions::new().mode(FormatMode::Integer);
+        let val = Bandwidth::new(1, 500_000_000);
+
+        assert_eq!(fmt(val, decimal), "1.5Gbps");
+        assert_eq!(fmt(val, integer), "1Gbps 500Mbps");
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(fmt(val, FormatOptions::default()), "1.5Gbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(fmt(val, FormatOptions::default()), "1Gbps 500Mbps");
+
+        // explicit unit
+        assert_eq!(fmt(val, decimal.unit(BandwidthUnit::Mbps)), "1500Mbps");
+        assert_eq!(fmt(val, decimal.unit(BandwidthUnit::Tbps)), "0.0015Tbps");
+        assert_eq!(
+            fmt(
+                Bandwidth::new(1, 500_000_005),
+                integer.unit(BandwidthUnit::Mbps)
+            ),
+            "1500Mbps 5bps"
+        );
+        assert_eq!(
+            fmt(Bandwidth::ZERO, decimal.unit(BandwidthUnit::Gbps)),
+            "0Gbps"
+        );
+        assert_eq!(
+            fmt(Bandwidth::ZERO, integer.unit(BandwidthUnit::Gbps)),
+            "0Gbps"
+        );
+        assert_eq!(
+            fmt(Bandwidth::MAX, decimal.unit(BandwidthUnit::Bps)),
+            Bandwidth::MAX.as_bps().to_string() + "bps"
+        );
+
+        // precision
+        assert_eq!(fmt(val, decimal.precision(0)), "2Gbps");
+        assert_eq!(fmt(val, decimal.precision(3)), "1.500Gbps");
+        assert_eq!(
+            fmt(Bandwidth::new(0, 150_024), decimal.precision(2)),
+            "150.02kbps"
+        );
+        assert_eq!(
+            fmt(Bandwidth::new(0, 999_999), decimal.precision(1)),
+            "1.0Mbps"
+        );
+        assert_eq!(fmt(Bandwidth::new(0, 5), decimal.precision(2)), "5.00bps");
+        assert_eq!(fmt(val, integer.precision(2)), "1Gbps 500Mbps");
+
+        // separator
+        assert_eq!(fmt(val, decimal.separator(" ")), "1.5 Gbps");
+        assert_eq!(fmt(val, integer.separator(" ")), "1 Gbps 500 Mbps");
+        assert_eq!(fmt(Bandwidth::ZERO, decimal.separator(" ")), "0 bps");
+        for opts in [decimal.separator(" "), integer.separator(" ")] {
+            assert_eq!(parse_bandwidth(&fmt(val, opts)), Ok(val));
+        }
+    }
+
+    #[test]
+    fn test_fmt_adaptive() {
+        struct Adaptive(FormattedBandwidth);
+        impl fmt::Display for Adaptive {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_adaptive(f)
+            }
+        }
+        let fmt = |bw, opts: FormatOptions| {
+            Adaptive(format_bandwidth_with_options(bw, &opts)).to_string()
+        };
+        let opts = FormatOptions::new();
+        assert_eq!(fmt(Bandwidth::ZERO, opts), "0bps");
+        assert_eq!(fmt(Bandwidth::new(1, 0), opts), "1Gbps");
+        assert_eq!(fmt(Bandwidth::new(9000, 0), opts), "9Tbps");
+        assert_eq!(fmt(Bandwidth::new(1, 500_000_000), opts), "1.5Gbps");
+        assert_eq!(fmt(Bandwidth::new(9420, 0), opts), "9.42Tbps");
+
+        let opts = FormatOptions::new().precision(3);
+        assert_eq!(fmt(Bandwidth::new(1, 0), opts), "1Gbps");
+        assert_eq!(fmt(Bandwidth::new(0, 32_000_000), opts), "32Mbps");
+        assert_eq!(fmt(Bandwidth::new(1, 500_000_000), opts), "1.500Gbps");
+
+        let opts = FormatOptions::new().unit(BandwidthUnit::Mbps);
+        assert_eq!(fmt(Bandwidth::new(1, 500_000_000), opts), "1500Mbps");
+        assert_eq!(fmt(Bandwidth::new(1, 500_000_001), opts), "1500.000001Mbps");
+
+        let opts = FormatOptions::new().mode(FormatMode::Auto).precision(1);
+        assert_eq!(
+            format_bandwidth_with_options(Bandwidth::new(2, 0), &opts).to_string(),
+            "2Gbps"
+        );
+        assert_eq!(
+            format_bandwidth_with_options(Bandwidth::new(2, 250_000_000), &opts).to_string(),
+            "2.3Gbps"
+        );
+    }
+
+    #[test]
+    fn test_formatted_precision() {
+        let val = Bandwidth::new(1, 500_000_000);
+        let decimal = |bw| format_bandwidth_with_options(bw, &FormatOptions::decimal());
+        assert_eq!(decimal(val).precision(0).to_string(), "2Gbps");
+        assert_eq!(decimal(val).precision(3).to_string(), "1.500Gbps");
+        assert_eq!(
+            decimal(Bandwidth::new(0, 150_024)).precision(1).to_string(),
+            "150.0kbps"
+        );
+        assert_eq!(
+            format_bandwidth_with_options(val, &FormatOptions::integer())
+                .precision(3)
+                .to_string(),
+            "1Gbps 500Mbps"
+        );
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(format_bandwidth(val).precision(0).to_string(), "2Gbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(
+            format_bandwidth(val).precision(0).to_string(),
+            "1Gbps 500Mbps"
+        );
     }
 
-    /// Enabling the `display-integer` feature will display integer values only
-    ///
-    /// This method is preserved for backward compatibility and custom formatting.
-    pub fn fmt_integer(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let gbps = self.0.as_gbps();
-        let bps = self.0.subgbps_bps();
+    #[test]
+    fn test_precision_carry() {
+        let decimal = |bw| format_bandwidth_with_options(bw, &FormatOptions::decimal());
+        for (bw, rounded, kept) in [
+            (Bandwidth::from_bps(999_500), "1Mbps", "999.5kbps"),
+            (Bandwidth::from_bps(999_500_000), "1Gbps", "999.5Mbps"),
+            (Bandwidth::new(999, 500_000_000), "1Tbps", "999.5Gbps"),
+            (Bandwidth::new(999_500, 0), "1Pbps", "999.5Tbps"),
+            (Bandwidth::new(999_500_000, 0), "1Ebps", "999.5Pbps"),
+        ] {
+            assert_eq!(format!("{:.0}", decimal(bw)), rounded);
+            assert_eq!(format!("{:.1}", decimal(bw)), kept);
+        }
+        for (bw, kept) in [
+            (Bandwidth::from_bps(999_499), "999kbps"),
+            (Bandwidth::from_bps(999_499_999), "999Mbps"),
+            (Bandwidth::new(999, 499_999_999), "999Gbps"),
+            (Bandwidth::new(999_499, 999_999_999), "999Tbps"),
+            (Bandwidth::new(999_499_999, 999_999_999), "999Pbps"),
+        ] {
+            assert_eq!(format!("{:.0}", decimal(bw)), kept);
+        }
+        assert_eq!(
+            format!("{:.0}", decimal(Bandwidth::from_bps(999_600_000))),
+            "1Gbps"
+        );
+        assert_eq!(
+            format!("{:.1}", decimal(Bandwidth::from_bps(999_999))),
+            "1.0Mbps"
+        );
+        assert_eq!(
+            format!("{:.2}", decimal(Bandwidth::from_bps(999_999_999))),
+            "1.00Gbps"
+        );
+        // a fixed unit is kept
+        let opts = FormatOptions::decimal().unit(BandwidthUnit::Mbps);
+        assert_eq!(
+            format!(
+                "{:.0}",
+                format_bandwidth_with_options(Bandwidth::from_bps(999_600_000), &opts)
+            ),
+            "1000Mbps"
+        );
+        // there is no unit above Ebps
+        assert_eq!(
+            format!(
+                "{:.0}",
+                decimal(Bandwidth::new(999_999_999_999, 600_000_000))
+            ),
+            "1000Ebps"
+        );
+    }
+
+    #[test]
+    fn test_format_speedtest() {
+        let fmt = |bw| format_bandwidth_speedtest(bw).to_string();
+        assert_eq!(fmt(Bandwidth::new(0, 150_230_000)), "150.23 Mbps");
+        assert_eq!(fmt(Bandwidth::new(0, 150_234_567)), "150.23 Mbps");
+        assert_eq!(fmt(Bandwidth::new(0, 150_236_000)), "150.24 Mbps");
+        assert_eq!(fmt(Bandwidth::new(1, 0)), "1.00 Gbps");
+        assert_eq!(fmt(Bandwidth::new(0, 5)), "5.00 bps");
+        assert_eq!(fmt(Bandwidth::ZERO), "0.00 bps");
+        assert_eq!(fmt(Bandwidth::new(9420, 0)), "9.42 Tbps");
+        // rounding up reaches the next unit
+        assert_eq!(fmt(Bandwidth::new(0, 999_999_999)), "1.00 Gbps");
+        assert_eq!(fmt(Bandwidth::new(0, 999_995_000)), "1.00 Gbps");
+        assert_eq!(fmt(Bandwidth::new(0, 999_994_999)), "999.99 Mbps");
+        assert_eq!(fmt(Bandwidth::from_bps(999_995)), "1.00 Mbps");
+
+        let fmt = |bw| format_bandwidth_speedtest_bps(bw).to_string();
+        assert_eq!(fmt(Bandwidth::from_kbps(1)), "1000.00 bps");
+        assert_eq!(fmt(Bandwidth::new(1, 5)), "1000000005.00 bps");
 
-        if gbps == 0 && bps == 0 {
-            f.write_str("0bps")?;
-            return Ok(());
+        for bw in [
+            Bandwidth::new(0, 150_230_000),
+            Bandwidth::new(1, 500_000_000),
+        ] {
+            let s = format_bandwidth_speedtest(bw).to_string();
+            assert_eq!(parse_bandwidth(&s), Ok(bw));
+            let s = format_bandwidth_speedtest_bps(bw).to_string();
+            assert_eq!(parse_bandwidth(&s), Ok(bw));
         }
+    }
 
-        let tbps = gbps / 1_000;
-        let gbps = gbps % 1_000;
+    #[test]
+    fn test_format_width() {
+        let vals = [
+            Bandwidth::from_bps(999),
+            Bandwidth::from_kbps(1_500),
+            Bandwidth::from_kbps(250),
+            Bandwidth::new(10, 0),
+        ];
+        let decimal = |bw| format_bandwidth_styled(bw, FormatMode::Decimal);
+        let column: Vec<String> = vals
+            .iter()
+            .map(|bw| format!("{:>15}", decimal(*bw)))
+            .collect();
+        assert_eq!(
+            column,
+            [
+                "         999bps",
+                "        1.5Mbps",
+                "        250kbps",
+                "         10Gbps",
+            ]
+        );
+        let val = decimal(Bandwidth::from_mbps(32));
+        assert_eq!(format!("{:15}", val), "         32Mbps");
+        assert_eq!(format!("{:<10}|", val), "32Mbps    |");
+        assert_eq!(format!("{:*^10}", val), "**32Mbps**");
+        assert_eq!(format!("{:+>8}", val), "++32Mbps");
+        assert_eq!(format!("{:>+8}", val), " +32Mbps");
+        assert_eq!(format!("{:>9.2}", val), "32.00Mbps");
+        assert_eq!(format!("{:>3}", val), "32Mbps");
+        assert_eq!(
+            format!("{:>7}", format_bandwidth_compact(Bandwidth::from_mbps(32))),
+            "    32M"
+        );
+    }
 
-        let mbps = bps / 1_000_000;
-        let kbps = bps / 1_000 % 1_000;
-        let bps = bps % 1_000;
+    #[test]
+    fn test_format_aligned() {
+        let vals = [
+            Bandwidth::from_bps(999),
+            Bandwidth::from_kbps(1_500),
+            Bandwidth::from_kbps(250),
+            Bandwidth::new(10, 0),
+            Bandwidth::new(0, 5),
+        ];
+        let column: Vec<String> = vals
+            .iter()
+            .map(|bw| format_bandwidth_aligned(*bw, 15).to_string())
+            .collect();
+        assert_eq!(
+            column,
+            [
+                "        999bps ",
+                "        1.5Mbps",
+                "        250kbps",
+                "         10Gbps",
+                "          5bps ",
+            ]
+        );
+        for s in &column {
+            assert_eq!(s.len(), 15);
+            // the numbers end in the same column
+            assert!(s.as_bytes()[10].is_ascii_digit(), "{:?}", s);
+        }
+        struct Aligned(FormattedBandwidth, usize);
 
-        let started = &mut false;
-        item(f, started, "Tbps", tbps as u32)?;
-        item(f, started, "Gbps", gbps as u32)?;
-        item(f, started, "Mbps", mbps)?;
-        item(f, started, "kbps", kbps)?;
-        item(f, started, "bps", bps)?;
-        Ok(())
+        impl fmt::Display for Aligned {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_aligned(f, self.1)
+            }
+        }
+
+        // always decimal, even with the integer form
+        let val = format_bandwidth_styled(Bandwidth::new(1, 500_000_000), FormatMode::Integer);
+        assert_eq!(Aligned(val, 8).to_string(), " 1.5Gbps");
+        let opts = FormatOptions::new().unit(BandwidthUnit::Mbps).precision(1);
+        let column: Vec<String> = [Bandwidth::from_kbps(1_500), Bandwidth::from_gbps(1)]
+            .iter()
+            .map(|bw| {
+                format!(
+                    "{:_>}",
+                    Aligned(format_bandwidth_with_options(*bw, &opts), 12)
+                )
+            })
+            .collect();
+        assert_eq!(column, ["_____1.5Mbps", "__1000.0Mbps"]);
+        let val = format_bandwidth(Bandwidth::from_bps(5));
+        assert_eq!(format!("{:.1}", Aligned(val.clone(), 8)), " 5.0bps ");
+        assert_eq!(Aligned(val, 2).to_string(), "5bps ");
     }
 
-    /// Disabling the `display-integer` feature will display decimal values
-    ///
-    /// This method is preserved for custom formatting.
-    pub fn fmt_decimal(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let gbps = self.0.as_gbps();
-        let bps = self.0.subgbps_bps();
+    #[test]
+    fn test_format_compact() {
+        let compact = |bps| format_bandwidth_compact(Bandwidth::from_bps(bps)).to_string();
+        assert_eq!(compact(0), "0");
+        assert_eq!(compact(999), "999");
+        assert_eq!(compact(1_000), "1K");
+        assert_eq!(compact(1_234), "1.23K");
+        assert_eq!(compact(1_235), "1.24K");
+        assert_eq!(compact(12_345), "12.3K");
+        assert_eq!(compact(123_456), "123K");
+        assert_eq!(compact(100_000_000), "100M");
+        assert_eq!(compact(999_499_999), "999M");
+        assert_eq!(compact(999_500_000), "1G");
+        assert_eq!(compact(9_999_000), "10M");
+        assert_eq!(compact(1_500_000_000), "1.5G");
+        assert_eq!(
+            format_bandwidth_compact(Bandwidth::new(9420, 0)).to_string(),
+            "9.42T"
+        );
+        assert_eq!(
+            format_bandwidth_compact(Bandwidth::new(1_500_000_000, 0)).to_string(),
+            "1.5E"
+        );
+        assert_eq!(
+            format_bandwidth_compact(Bandwidth::new(1_234_567_890_000, 0)).to_string(),
+            "1235E"
+        );
+        // the largest values truncate rather than round past the maximum
+        assert_eq!(
+            format_bandwidth_compact(Bandwidth::MAX).to_string(),
+            "18446744073E"
+        );
+    }
 
-        if gbps == 0 && bps == 0 {
-            f.write_str("0bps")?;
-            return Ok(());
+    #[test]
+    fn test_compact_roundtrip() {
+        for bw in [
+            Bandwidth::from_bps(7),
+            Bandwidth::from_bps(1_004),
+            Bandwidth::from_bps(123_456),
+            Bandwidth::from_kbps(99_960),
+            Bandwidth::new(0, 999_999_999),
+            Bandwidth::new(1, 5),
+            Bandwidth::new(9420, 0),
+            Bandwidth::new(123_456_789, 123),
+            Bandwidth::new(u64::MAX / 2, 0),
+            Bandwidth::new(u64::MAX, 0),
+            Bandwidth::MAX,
+        ] {
+            let s = format_bandwidth_compact(bw).to_string();
+            assert!(s.len() <= 5 || bw.as_gbps() > 999_000_000_000, "{}", s);
+            let parsed = parse_bandwidth_compact(&s).unwrap();
+            let (bps, parsed) = (bw.as_bps() as f64, parsed.as_bps() as f64);
+            assert!((parsed - bps).abs() <= bps * 0.005, "{} {}", s, bps);
+        }
+        assert_eq!(
+            format_bandwidth_compact(Bandwidth::MAX).to_string(),
+            "18446744073E"
+        );
+        assert_eq!(
+            parse_bandwidth_compact("18446744073E"),
+            Ok(Bandwidth::new(18_446_744_073_000_000_000, 0))
+        );
+        // three significant digits round-trip exactly
+        for bps in [
+            0,
+            7,
+            150_000,
+            1_230_000,
+            45_600_000_000,
+            999_000_000_000_000_000,
+        ] {
+            let bw = Bandwidth::from_bps(bps);
+            let s = format_bandwidth_compact(bw).to_string();
+            assert_eq!(parse_bandwidth_compact(&s), Ok(bw));
         }
+    }
 
-        let tbps = gbps / 1_000;
-        let gbps = gbps % 1_000;
+    #[test]
+    fn test_validate() {
+        for s in [
+            "1Gbps",
+            "9Tbps 420Gbps",
+            "150.02456kbps",
+            "1_000bps",
+            "",
+            "   ",
+            "1Gbit",
+            "1GBps",
+            "Gbps",
+            "1",
+            "1.2.3Gbps",
+            "18446744073709551616bps",
+            "18446744073709551615Ebps",
+            "18446744073Ebps 1Ebps",
+        ] {
+            assert_eq!(
+                validate_bandwidth_string(s),
+                parse_bandwidth(s).map(|_| ()),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            validate_bandwidth_string("18446744073Ebps 1Ebps"),
+            Err(Error::NumberOverflow)
+        );
+    }
 
-        let mbps = (bps / 1_000_000) as u64;
-        let kbps = (bps / 1_000 % 1_000) as u64;
-        let bps = (bps % 1_000) as u64;
+    #[test]
+    fn test_case_insensitive() {
+        let parse = parse_bandwidth_case_insensitive;
+        for s in [
+            "1Gbps", "1gbps", "1GBPS", "1GBPs", "1gBpS", "1GBIT/S", "1gB/S",
+        ] {
+            assert_eq!(parse(s), Ok(Bandwidth::from_gbps(1)), "{}", s);
+        }
+        assert_eq!(parse("1KBPS"), Ok(Bandwidth::from_kbps(1)));
+        assert_eq!(parse("1BPS"), Ok(Bandwidth::from_bps(1)));
+        assert_eq!(parse("1 TBPS 5 MBPS"), Ok(Bandwidth::new(1_000, 5_000_000)));
+        assert_eq!(parse("1EBIT/S"), Ok(Bandwidth::new(1_000_000_000, 0)));
+        // exact byte rate spellings keep their meaning
+        assert_eq!(parse("1GBps"), Ok(Bandwidth::from_gbps(8)));
+        assert_eq!(parse("1MB/s"), Ok(Bandwidth::from_mbps(8)));
+        assert_eq!(parse("1Bps"), Ok(Bandwidth::from_bps(8)));
+        assert!(matches!(
+            parse("1GBYTE/S"),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 8,
+                ..
+            })
+        ));
 
-        let largest_unit = if tbps > 0 {
-            LargestUnit::Tbps
-        } else if gbps > 0 {
-            LargestUnit::Gbps
-        } else if mbps > 0 {
-            LargestUnit::Mbps
-        } else if kbps > 0 {
-            LargestUnit::Kbps
-        } else {
-            LargestUnit::Bps
-        };
+        assert!(parse_bandwidth("1GBPS").is_err());
+        let opts = ParseOptions::new()
+            .case_insensitive(true)
+            .allow_byte_units(false);
+        assert_eq!(
+            parse_bandwidth_with_options("1GBPs", &opts),
+            Ok(Bandwidth::from_gbps(1))
+        );
+        assert!(parse_bandwidth_with_options("1GBps", &opts).is_err());
+    }
 
-        let values = [bps, kbps, mbps, gbps, tbps];
-        let mut index = largest_unit as usize;
-        let mut zeros = 0;
-        let mut dot = true;
-        write!(f, "{}", values[index])?;
-        loop {
-            if index == 0 {
-                write!(f, "{}", largest_unit)?;
-                break;
-            }
-            index -= 1;
-            let value = values[index];
-            if value == 0 {
-                zeros += 3;
-                continue;
-            } else {
-                if dot {
-                    f.write_str(".")?;
-                    dot = false;
-                }
-                if zeros > 0 {
-                    write!(f, "{:0width$}", 0, width = zeros)?;
-                    zeros = 0;
-                }
-                if value % 10 != 0 {
-                    write!(f, "{:03}", value)?;
-                } else if value % 100 != 0 {
-                    write!(f, "{:02}", value / 10)?;
-                    zeros += 1;
+    #[test]
+    fn test_parse_compact() {
+        assert_eq!(
+            parse_bandwidth_compact("1.5G"),
+            Ok(Bandwidth::new(1, 500_000_000))
+        );
+        assert_eq!(parse_bandwidth_compact("2T"), Ok(Bandwidth::new(2_000, 0)));
+        assert_eq!(
+            parse_bandwidth_compact("1P"),
+            Ok(Bandwidth::new(1_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth_compact("1E"),
+            Ok(Bandwidth::new(1_000_000_000, 0))
+        );
+        assert_eq!(parse_bandwidth_compact("999"), Ok(Bandwidth::from_bps(999)));
+        assert_eq!(
+            parse_bandwidth_compact("1Gbps"),
+            Ok(Bandwidth::from_gbps(1))
+        );
+        assert!(parse_bandwidth_compact("1B").is_err());
+        let opts = ParseOptions::new().allow_bare_prefix(false);
+        assert!(parse_bandwidth_with_options("1G", &opts).is_err());
+    }
+
+    #[test]
+    fn test_format_styled() {
+        let val = Bandwidth::new(9420, 5);
+        let decimal = format_bandwidth_styled(val, FormatMode::Decimal);
+        let integer = format_bandwidth_styled(val, FormatMode::Integer);
+        assert_eq!(decimal.to_string(), "9.420000000005Tbps");
+        assert_eq!(integer.to_string(), "9Tbps 420Gbps 5bps");
+        assert_eq!(
+            format_bandwidth_styled(val, FormatMode::Auto).to_string(),
+            "9.420000000005Tbps"
+        );
+        assert_eq!(
+            format_bandwidth_styled(Bandwidth::new(2, 0), FormatMode::Auto).to_string(),
+            "2Gbps"
+        );
+        assert_eq!(
+            format_bandwidth_styled(val, FormatMode::default()).to_string(),
+            format_bandwidth(val).to_string()
+        );
+        // the values compare equal whatever their form
+        assert_eq!(decimal, integer);
+    }
+
+    #[test]
+    fn test_format_with_unit() {
+        let fmt = |bw, unit| format_bandwidth_with_unit(bw, unit).to_string();
+        let mbps = BandwidthUnit::Mbps;
+        #[cfg(not(feature = "display-integer"))]
+        {
+            assert_eq!(fmt(Bandwidth::new(1, 500_000_000), mbps), "1500Mbps");
+            assert_eq!(fmt(Bandwidth::from_kbps(250), mbps), "0.25Mbps");
+            assert_eq!(fmt(Bandwidth::ZERO, mbps), "0Mbps");
+            assert_eq!(
+                fmt(Bandwidth::from_gbps(2), BandwidthUnit::Bps),
+                "2000000000bps"
+            );
+        }
+        #[cfg(feature = "display-integer")]
+        {
+            assert_eq!(fmt(Bandwidth::new(1, 500_000_000), mbps), "1500Mbps");
+            assert_eq!(fmt(Bandwidth::new(0, 1_250_000), mbps), "1Mbps 250kbps");
+            assert_eq!(fmt(Bandwidth::ZERO, mbps), "0Mbps");
+        }
+
+        struct Decimal(FormattedBandwidth);
+        impl fmt::Display for Decimal {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_decimal(f)
+            }
+        }
+        struct Integer(FormattedBandwidth);
+        impl fmt::Display for Integer {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_integer(f)
+            }
+        }
+        let val = format_bandwidth_with_unit(Bandwidth::new(1, 5), mbps);
+        assert_eq!(Decimal(val.clone()).to_string(), "1000.000005Mbps");
+        assert_eq!(Integer(val).to_string(), "1000Mbps 5bps");
+    }
+
+    #[test]
+    fn test_format_spec_precision() {
+        let val = format_bandwidth_with_options(
+            Bandwidth::new(1, 456_789_000),
+            &FormatOptions::decimal(),
+        );
+        assert_eq!(format!("{}", val), "1.456789Gbps");
+        assert_eq!(format!("{:.0}", val), "1Gbps");
+        assert_eq!(format!("{:.2}", val), "1.46Gbps");
+        assert_eq!(format!("{:.4}", val), "1.4568Gbps");
+        assert_eq!(format!("{:.8}", val), "1.45678900Gbps");
+        assert_eq!(format!("{:+.1}", val), "+1.5Gbps");
+
+        let val = format_bandwidth_with_options(
+            Bandwidth::new(0, 999_999_999),
+            &FormatOptions::decimal(),
+        );
+        assert_eq!(format!("{:.0}", val), "1Gbps");
+        assert_eq!(format!("{:.2}", val), "1.00Gbps");
+        let val = format_bandwidth_with_options(Bandwidth::new(0, 5), &FormatOptions::decimal());
+        assert_eq!(format!("{:.2}", val), "5.00bps");
+
+        // the format spec overrides the options
+        let val = format_bandwidth_with_options(
+            Bandwidth::new(1, 500_000_000),
+            &FormatOptions::decimal().precision(3),
+        );
+        assert_eq!(format!("{}", val), "1.500Gbps");
+        assert_eq!(format!("{:.1}", val), "1.5Gbps");
+
+        // integer form has no fractional digits
+        let val = format_bandwidth_with_options(
+            Bandwidth::new(1, 500_000_000),
+            &FormatOptions::integer(),
+        );
+        assert_eq!(format!("{:.2}", val), "1Gbps 500Mbps");
+    }
+
+    #[test]
+    fn test_multiplier_bps() {
+        let mut multiplier = 1;
+        for unit in BandwidthUnit::ALL {
+            assert_eq!(unit.multiplier_bps(), multiplier);
+            assert_eq!(u128::from(unit.multiplier_bps()), unit.scale());
+            multiplier = multiplier.saturating_mul(1000);
+        }
+        assert_eq!(
+            BandwidthUnit::Ebps.multiplier_bps(),
+            1_000_000_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_unit_from_str() {
+        let aliases = BandwidthUnit::all_aliases();
+        assert_eq!(aliases.len(), 51);
+        let (bits, bytes) = aliases.split_at(39);
+        let mut units = Vec::new();
+        for alias in bits {
+            let unit: BandwidthUnit = alias.parse().unwrap();
+            assert_eq!(
+                parse_bandwidth(&format!("1{}", alias)),
+                Ok(Bandwidth::from_bps(unit.multiplier_bps()))
+            );
+            assert!(alias.eq_ignore_ascii_case(&unit.to_string()) || alias.contains('/'));
+            units.push(unit);
+        }
+        assert_eq!(&units[..3], [BandwidthUnit::Bps; 3]);
+        for (i, unit) in BandwidthUnit::ALL[1..].iter().enumerate() {
+            assert_eq!(&units[3 + i * 6..][..6], [*unit; 6]);
+            // the canonical spelling comes first
+            assert_eq!(aliases[3 + i * 6], unit.to_string());
+        }
+        for unit in BandwidthUnit::ALL {
+            assert_eq!(unit.to_string().parse(), Ok(unit));
+        }
+        // byte rates give the unit of their prefix, like the spans
+        for alias in bytes {
+            let unit: BandwidthUnit = alias.parse().unwrap();
+            assert_eq!(
+                parse_bandwidth(&format!("1{}", alias)),
+                Ok(Bandwidth::from_bps(8 * unit.multiplier_bps()))
+            );
+            #[cfg(feature = "alloc")]
+            assert_eq!(
+                parse_bandwidth_spans(&format!("1{}", alias)).unwrap()[0].unit,
+                unit
+            );
+        }
+        assert_eq!("MBps".parse(), Ok(BandwidthUnit::Mbps));
+        assert_eq!("B/s".parse(), Ok(BandwidthUnit::Bps));
+
+        assert_eq!("".parse::<BandwidthUnit>(), Err(Error::Empty));
+        for s in ["GBPS", " Gbps", "Gbit", "1Gbps", "pps", "MiB/s"] {
+            assert_eq!(
+                s.parse::<BandwidthUnit>(),
+                Err(Error::UnknownUnitName { unit: s.into() })
+            );
+        }
+        assert_eq!(
+            "Gbit".parse::<BandwidthUnit>().unwrap_err().to_string(),
+            parse_bandwidth("1Gbit").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_formatted_map() {
+        let half = Bandwidth::new(0, 500_000_000);
+        let val = format_bandwidth_with_options(Bandwidth::new(1, 0), &FormatOptions::decimal());
+        assert_eq!(
+            val.clone()
+                .map(|b| b.checked_add(half).unwrap())
+                .to_string(),
+            "1.5Gbps"
+        );
+        assert_eq!(
+            val.clone()
+                .try_map(|b| b.checked_sub(half))
+                .map(|v| v.to_string()),
+            Some("500Mbps".to_string())
+        );
+        assert_eq!(val.try_map(|b| b.checked_sub(Bandwidth::new(2, 0))), None);
+
+        // the options are kept
+        let val = format_bandwidth_with_options(Bandwidth::new(1, 0), &FormatOptions::integer())
+            .map(|b| b + half);
+        assert_eq!(val.to_string(), "1Gbps 500Mbps");
+    }
+
+    #[test]
+    fn test_formatted_from() {
+        let fb: FormattedBandwidth = Bandwidth::new(1, 0).into();
+        assert_eq!(fb.to_string(), "1Gbps");
+        assert_eq!(*fb.get_ref(), Bandwidth::new(1, 0));
+
+        let bws = [Bandwidth::from_mbps(32), Bandwidth::from_gbps(10)];
+        let strs: Vec<String> = bws
+            .iter()
+            .map(FormattedBandwidth::from)
+            .map(|fb| fb.to_string())
+            .collect();
+        assert_eq!(strs, ["32Mbps", "10Gbps"]);
+        assert_eq!(
+            FormattedBandwidth::from(Bandwidth::new(1, 500_000_000)).to_string(),
+            format_bandwidth(Bandwidth::new(1, 500_000_000)).to_string()
+        );
+    }
+
+    #[test]
+    fn test_formatted_ord() {
+        let mut vals = [
+            format_bandwidth(Bandwidth::from_mbps(100)),
+            format_bandwidth(Bandwidth::from_gbps(1)),
+            format_bandwidth(Bandwidth::from_mbps(500)),
+        ];
+        vals.sort();
+        let sorted: Vec<String> = vals.iter().map(|v| v.to_string()).collect();
+        assert_eq!(sorted, ["100Mbps", "500Mbps", "1Gbps"]);
+
+        let a = format_bandwidth(Bandwidth::new(1, 0));
+        let b = format_bandwidth_with_options(Bandwidth::new(1, 0), &FormatOptions::integer());
+        assert_eq!(a, b);
+        assert!(format_bandwidth(Bandwidth::new(0, 999_999_999)) < a);
+        assert!(format_bandwidth(Bandwidth::new(1, 1)) > b);
+    }
+
+    #[test]
+    fn test_format_diff() {
+        let diff = |before, after| format_bandwidth_diff(before, after);
+        let up = diff(Bandwidth::from_mbps(100), Bandwidth::new(0, 112_500_000));
+        assert!(up.is_increase());
+        assert!(!up.is_zero());
+        assert_eq!(up.magnitude(), Bandwidth::from_kbps(12_500));
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(up.to_string(), "+12.5Mbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(up.to_string(), "+12Mbps 500kbps");
+
+        let down = diff(Bandwidth::new(0, 112_500_000), Bandwidth::from_mbps(100));
+        assert!(!down.is_increase());
+        assert!(!down.is_zero());
+        assert_eq!(down.magnitude(), Bandwidth::from_kbps(12_500));
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(down.to_string(), "-12.5Mbps");
+
+        let same = diff(Bandwidth::from_gbps(1), Bandwidth::from_gbps(1));
+        assert!(!same.is_increase());
+        assert!(same.is_zero());
+        assert_eq!(same.magnitude(), Bandwidth::ZERO);
+        assert_eq!(same.to_string(), "0bps");
+
+        // across a unit boundary
+        let up = diff(Bandwidth::from_kbps(999), Bandwidth::from_kbps(1_001));
+        assert_eq!(up.to_string(), "+2kbps");
+        let down = diff(Bandwidth::from_kbps(1_001), Bandwidth::from_kbps(999));
+        assert_eq!(down.to_string(), "-2kbps");
+        let up = diff(Bandwidth::from_kbps(999), Bandwidth::from_mbps(2));
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(up.to_string(), "+1.001Mbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(up.to_string(), "+1Mbps 1kbps");
+
+        assert_eq!(
+            diff(Bandwidth::ZERO, Bandwidth::MAX).magnitude(),
+            Bandwidth::MAX
+        );
+        assert_eq!(
+            diff(Bandwidth::MAX, Bandwidth::ZERO).magnitude(),
+            Bandwidth::MAX
+        );
+
+        let down = diff(Bandwidth::from_kbps(1_001), Bandwidth::from_kbps(999));
+        assert_eq!(format!("{:>8}", down), "  -2kbps");
+        assert_eq!(format!("{:<8}|", down), "-2kbps  |");
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(format!("{:.1}", down), "-2.0kbps");
+        assert_eq!(format!("{:+}", down), "-2kbps");
+    }
+
+    #[test]
+    fn test_formatted_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::{BTreeMap, HashSet};
+        use std::hash::{Hash, Hasher};
+
+        fn hash(val: &FormattedBandwidth) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            val.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let val = Bandwidth::new(1, 500_000_000);
+        let decimal = format_bandwidth_styled(val, FormatMode::Decimal);
+        let integer = format_bandwidth_styled(val, FormatMode::Integer);
+        assert_eq!(hash(&decimal), hash(&integer));
+
+        let set: HashSet<FormattedBandwidth> =
+            [decimal, integer, format_bandwidth(Bandwidth::ZERO)]
+                .into_iter()
+                .collect();
+        assert_eq!(set.len(), 2);
+
+        let mut map = BTreeMap::new();
+        map.insert(format_bandwidth_styled(val, FormatMode::Decimal), "a");
+        map.insert(format_bandwidth_styled(val, FormatMode::Integer), "b");
+        map.insert(FormattedBandwidth::default(), "c");
+        assert_eq!(map.len(), 2);
+        let keys: Vec<String> = map.keys().map(|k| k.to_string()).collect();
+        assert_eq!(keys, ["0bps", "1.5Gbps"]);
+        assert_eq!(map[&format_bandwidth(val)], "b");
+
+        assert_eq!(*FormattedBandwidth::default().get_ref(), Bandwidth::ZERO);
+        assert_eq!(
+            FormattedBandwidth::default(),
+            format_bandwidth(Bandwidth::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_fmt_with_options() {
+        struct WithOptions(FormattedBandwidth, FormatOptions);
+        impl fmt::Display for WithOptions {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_with_options(f, &self.1)
+            }
+        }
+        struct Direct(FormattedBandwidth, bool);
+        impl fmt::Display for Direct {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.1 {
+                    self.0.fmt_integer(f)
                 } else {
-                    write!(f, "{}", value / 100)?;
-                    zeros += 2;
+                    self.0.fmt_decimal(f)
                 }
             }
         }
-        Ok(())
+
+        for bw in [
+            Bandwidth::ZERO,
+            Bandwidth::new(0, 150_024),
+            Bandwidth::new(1, 500_000_000),
+            Bandwidth::new(9420, 0),
+            Bandwidth::MAX,
+        ] {
+            let val = format_bandwidth(bw);
+            assert_eq!(
+                WithOptions(val.clone(), FormatOptions::decimal()).to_string(),
+                Direct(val.clone(), false).to_string()
+            );
+            assert_eq!(
+                WithOptions(val.clone(), FormatOptions::integer()).to_string(),
+                Direct(val.clone(), true).to_string()
+            );
+            assert_eq!(
+                WithOptions(val.clone(), FormatOptions::default()).to_string(),
+                val.to_string()
+            );
+        }
+
+        // the passed options replace the stored ones
+        let opts = FormatOptions::decimal().separator(" ");
+        let val = format_bandwidth_with_options(Bandwidth::new(1, 0), &opts);
+        assert_eq!(val.to_string(), "1 Gbps");
+        assert_eq!(
+            WithOptions(val, FormatOptions::decimal()).to_string(),
+            "1Gbps"
+        );
+    }
+
+    #[test]
+    fn test_sign_plus() {
+        assert_eq!(
+            format!("{:+}", format_bandwidth(Bandwidth::new(1, 0))),
+            "+1Gbps"
+        );
+        assert_eq!(
+            format!("{}", format_bandwidth(Bandwidth::new(1, 0))),
+            "1Gbps"
+        );
+        assert_eq!(format!("{:+}", format_bandwidth(Bandwidth::ZERO)), "+0bps");
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(
+            format!("{:+}", format_bandwidth(Bandwidth::new(1, 500_000_000))),
+            "+1.5Gbps"
+        );
+        #[cfg(feature = "display-integer")]
+        assert_eq!(
+            format!("{:+}", format_bandwidth(Bandwidth::new(1, 500_000_000))),
+            "+1Gbps 500Mbps"
+        );
+    }
+
+    #[test]
+    fn test_affixes() {
+        let bw = Bandwidth::new(1, 0);
+        assert_eq!(format_bandwidth(bw).with_prefix("").to_string(), "1Gbps");
+        assert_eq!(
+            format_bandwidth(bw).with_prefix("limit=").to_string(),
+            "limit=1Gbps"
+        );
+        assert_eq!(format_bandwidth(bw).with_prefix(42).to_string(), "421Gbps");
+        assert_eq!(
+            format_bandwidth(bw).with_suffix(" (").to_string(),
+            "1Gbps ("
+        );
+        let peak = format_bandwidth(Bandwidth::new(2, 0));
+        assert_eq!(
+            format_bandwidth(bw)
+                .with_suffix(format_args!(", peak {}", peak))
+                .to_string(),
+            "1Gbps, peak 2Gbps"
+        );
+    }
+
+    #[test]
+    fn test_or_default() {
+        let default = Bandwidth::from_mbps(100);
+        assert_eq!(
+            parse_bandwidth_or_default("1Gbps", default),
+            Bandwidth::new(1, 0)
+        );
+        assert_eq!(parse_bandwidth_or_default("", default), default);
+        assert_eq!(parse_bandwidth_or_default("1Gbit", default), default);
+        assert_eq!(parse_bandwidth_or_zero("32Mbps"), Bandwidth::from_mbps(32));
+        assert_eq!(parse_bandwidth_or_zero("fast"), Bandwidth::ZERO);
+        assert_eq!(parse_bandwidth_or_max("0bps"), Bandwidth::ZERO);
+        assert_eq!(parse_bandwidth_or_max("unlimited"), Bandwidth::MAX);
+    }
+
+    #[test]
+    fn test_partial() {
+        assert_eq!(
+            parse_bandwidth_partial("1Gbps"),
+            Ok((Bandwidth::new(1, 0), ""))
+        );
+        assert_eq!(
+            parse_bandwidth_partial("1Gbps10Mbps, 20Mbps"),
+            Ok((Bandwidth::new(1, 10_000_000), ", 20Mbps"))
+        );
+        assert_eq!(
+            parse_bandwidth_partial("1 000 Mbps 10 s"),
+            Ok((Bandwidth::new(1, 0), " 10 s"))
+        );
+        assert_eq!(
+            parse_bandwidth_partial("1Gbps 3"),
+            Ok((Bandwidth::new(1, 0), " 3"))
+        );
+        assert_eq!(
+            parse_bandwidth_partial("1Gbps "),
+            Ok((Bandwidth::new(1, 0), " "))
+        );
+        assert_eq!(parse_bandwidth_partial(""), Err(Error::Empty));
+        assert_eq!(parse_bandwidth_partial("  "), Err(Error::Empty));
+        assert_eq!(
+            parse_bandwidth_partial(" ,1Gbps"),
+            Err(Error::NumberExpected(1))
+        );
+        assert_eq!(
+            parse_bandwidth_partial("10s 1Gbps"),
+            Err(Error::UnknownUnit {
+                start: 2,
+                end: 3,
+                unit: "s".into(),
+                value: 10
+            })
+        );
+        assert_eq!(
+            parse_bandwidth_partial("18446744073709551615Gbps 1Gbps"),
+            Err(Error::NumberOverflow)
+        );
     }
-}
 
-impl fmt::Display for FormattedBandwidth {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        #[cfg(not(feature = "display-integer"))]
-        self.fmt_decimal(f)?;
-        #[cfg(feature = "display-integer")]
-        self.fmt_integer(f)?;
-        Ok(())
+    #[test]
+    fn test_parse_fraction() {
+        let floor = RoundingMode::Floor;
+        assert_eq!(parse_fraction(9, 1, 0, floor), 0);
+        assert_eq!(parse_fraction(999, 3, 0, floor), 0);
+        assert_eq!(parse_fraction(0, 0, 0, floor), 0);
+        assert_eq!(parse_fraction(5, 1, 0, RoundingMode::Round), 1);
+        assert_eq!(parse_fraction(4, 1, 0, RoundingMode::Round), 0);
+        assert_eq!(parse_fraction(1, 3, 0, RoundingMode::Ceil), 1);
+        assert_eq!(parse_fraction(0, 3, 0, RoundingMode::Ceil), 0);
+        assert_eq!(parse_fraction(5, 1, 3, floor), 500);
+        assert_eq!(parse_fraction(12345, 5, 3, floor), 123);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use bandwidth::Bandwidth;
+    #[test]
+    fn test_sub_bps_truncated() {
+        assert_eq!(parse_bandwidth("1.5bps"), Ok(Bandwidth::new(0, 1)));
+        assert_eq!(parse_bandwidth("1.999bps"), Ok(Bandwidth::new(0, 1)));
+        assert_eq!(parse_bandwidth("0.9bps"), Ok(Bandwidth::ZERO));
+        assert_eq!(parse_bandwidth("1.9bit/s"), Ok(Bandwidth::new(0, 1)));
+        assert_eq!(
+            parse_bandwidth("1kbps 1.9bps"),
+            Ok(Bandwidth::new(0, 1_001))
+        );
+        assert_eq!(parse_bandwidth("1.0019kbps"), Ok(Bandwidth::new(0, 1_001)));
+    }
 
+    #[cfg(feature = "bare-prefix")]
     #[test]
-    fn test_units() {
-        assert_eq!(parse_bandwidth("1bps"), Ok(Bandwidth::new(0, 1)));
-        assert_eq!(parse_bandwidth("2bit/s"), Ok(Bandwidth::new(0, 2)));
-        assert_eq!(parse_bandwidth("15b/s"), Ok(Bandwidth::new(0, 15)));
-        assert_eq!(parse_bandwidth("51kbps"), Ok(Bandwidth::new(0, 51_000)));
-        assert_eq!(parse_bandwidth("79Kbps"), Ok(Bandwidth::new(0, 79_000)));
-        assert_eq!(parse_bandwidth("81kbit/s"), Ok(Bandwidth::new(0, 81_000)));
-        assert_eq!(parse_bandwidth("100Kbit/s"), Ok(Bandwidth::new(0, 100_000)));
-        assert_eq!(parse_bandwidth("150kb/s"), Ok(Bandwidth::new(0, 150_000)));
-        assert_eq!(parse_bandwidth("410Kb/s"), Ok(Bandwidth::new(0, 410_000)));
-        assert_eq!(parse_bandwidth("12Mbps"), Ok(Bandwidth::new(0, 12_000_000)));
-        assert_eq!(parse_bandwidth("16mbps"), Ok(Bandwidth::new(0, 16_000_000)));
+    fn test_bare_prefix() {
+        assert_eq!(parse_bandwidth("10G"), Ok(Bandwidth::new(10, 0)));
+        assert_eq!(parse_bandwidth("100M"), Ok(Bandwidth::from_mbps(100)));
+        assert_eq!(parse_bandwidth("100m"), Ok(Bandwidth::from_mbps(100)));
+        assert_eq!(parse_bandwidth("1T"), Ok(Bandwidth::new(1_000, 0)));
+        assert_eq!(parse_bandwidth("1.5k"), Ok(Bandwidth::from_bps(1_500)));
+        assert_eq!(parse_bandwidth("1K"), Ok(Bandwidth::from_kbps(1)));
         assert_eq!(
-            parse_bandwidth("24Mbit/s"),
-            Ok(Bandwidth::new(0, 24_000_000))
+            parse_bandwidth("1G 500M"),
+            Ok(Bandwidth::new(1, 500_000_000))
         );
         assert_eq!(
-            parse_bandwidth("36mbit/s"),
-            Ok(Bandwidth::new(0, 36_000_000))
+            parse_bandwidth("1Gbps 5"),
+            Err(Error::UnknownUnit {
+                start: 7,
+                end: 7,
+                unit: "".into(),
+                value: 5,
+            })
         );
-        assert_eq!(parse_bandwidth("48Mb/s"), Ok(Bandwidth::new(0, 48_000_000)));
-        assert_eq!(parse_bandwidth("96mb/s"), Ok(Bandwidth::new(0, 96_000_000)));
-        assert_eq!(parse_bandwidth("2Gbps"), Ok(Bandwidth::new(2, 0)));
-        assert_eq!(parse_bandwidth("4gbps"), Ok(Bandwidth::new(4, 0)));
-        assert_eq!(parse_bandwidth("6Gbit/s"), Ok(Bandwidth::new(6, 0)));
-        assert_eq!(parse_bandwidth("8gbit/s"), Ok(Bandwidth::new(8, 0)));
-        assert_eq!(parse_bandwidth("16Gb/s"), Ok(Bandwidth::new(16, 0)));
-        assert_eq!(parse_bandwidth("40gb/s"), Ok(Bandwidth::new(40, 0)));
-        assert_eq!(parse_bandwidth("1Tbps"), Ok(Bandwidth::new(1_000, 0)));
-        assert_eq!(parse_bandwidth("2tbps"), Ok(Bandwidth::new(2_000, 0)));
-        assert_eq!(parse_bandwidth("4Tbit/s"), Ok(Bandwidth::new(4_000, 0)));
-        assert_eq!(parse_bandwidth("8tbit/s"), Ok(Bandwidth::new(8_000, 0)));
-        assert_eq!(parse_bandwidth("16Tb/s"), Ok(Bandwidth::new(16_000, 0)));
-        assert_eq!(parse_bandwidth("32tb/s"), Ok(Bandwidth::new(32_000, 0)));
+        assert_eq!(
+            parse_bandwidth("1B"),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 2,
+                unit: "B".into(),
+                value: 1,
+            })
+        );
+        assert_eq!(parse_bandwidth("1Gbps"), Ok(Bandwidth::new(1, 0)));
     }
 
+    #[cfg(not(feature = "bare-prefix"))]
     #[test]
-    fn test_decimal() {
-        assert_eq!(parse_bandwidth("1.5bps"), Ok(Bandwidth::new(0, 1)));
-        assert_eq!(parse_bandwidth("2.5bit/s"), Ok(Bandwidth::new(0, 2)));
-        assert_eq!(parse_bandwidth("15.5b/s"), Ok(Bandwidth::new(0, 15)));
-        assert_eq!(parse_bandwidth("51.6kbps"), Ok(Bandwidth::new(0, 51_600)));
-        assert_eq!(parse_bandwidth("79.78Kbps"), Ok(Bandwidth::new(0, 79_780)));
+    fn test_bare_prefix_disabled() {
         assert_eq!(
-            parse_bandwidth("81.923kbit/s"),
-            Ok(Bandwidth::new(0, 81_923))
+            parse_bandwidth("10G"),
+            Err(Error::UnknownUnit {
+                start: 2,
+                end: 3,
+                unit: "G".into(),
+                value: 10,
+            })
         );
+    }
+
+    #[test]
+    fn test_byte_units() {
+        let parse = parse_bandwidth;
+        assert_eq!(parse("1GBps"), Ok(Bandwidth::new(8, 0)));
+        assert_eq!(parse("32MBps"), Ok(Bandwidth::new(0, 256_000_000)));
+        assert_eq!(parse("1kBps"), Ok(Bandwidth::from_kbps(8)));
+        assert_eq!(parse("1KB/s"), Ok(Bandwidth::from_kbps(8)));
+        assert_eq!(parse("3Bps"), Ok(Bandwidth::from_bps(24)));
+        assert_eq!(parse("1TBps"), Ok(Bandwidth::new(8_000, 0)));
+        assert_eq!(parse("1.5MB/s"), Ok(Bandwidth::from_mbps(12)));
+        // fractions of a byte are kept, down to 1bps
+        assert_eq!(parse("1.5Bps"), Ok(Bandwidth::from_bps(12)));
+        assert_eq!(parse("1.5 Bps"), Ok(Bandwidth::from_bps(12)));
+        assert_eq!(parse("0.125Bps"), Ok(Bandwidth::from_bps(1)));
+        assert_eq!(parse("1.9Bps"), Ok(Bandwidth::from_bps(15)));
+        assert_eq!(parse("0.1Bps"), Ok(Bandwidth::ZERO));
+        let opts = ParseOptions::new().rounding_mode(RoundingMode::Round);
         assert_eq!(
-            parse_bandwidth("100.1234Kbit/s"),
-            Ok(Bandwidth::new(0, 100_123))
+            parse_bandwidth_with_options("0.1Bps", &opts),
+            Ok(Bandwidth::from_bps(1))
         );
         assert_eq!(
-            parse_bandwidth("150.12345kb/s"),
-            Ok(Bandwidth::new(0, 150_123))
+            parse_bandwidth_with_options("1.9Bps", &opts),
+            Ok(Bandwidth::from_bps(15))
         );
+        let opts = ParseOptions::new().rounding_mode(RoundingMode::Ceil);
         assert_eq!(
-            parse_bandwidth("410.123456Kb/s"),
-            Ok(Bandwidth::new(0, 410_123))
+            parse_bandwidth_with_options("1.9Bps", &opts),
+            Ok(Bandwidth::from_bps(16))
         );
         assert_eq!(
-            parse_bandwidth("12.123Mbps"),
-            Ok(Bandwidth::new(0, 12_123_000))
+            parse_bandwidth_with_options("0.125Bps", &opts),
+            Ok(Bandwidth::from_bps(1))
         );
+        assert_eq!(parse("1MBps 1Mbps"), Ok(Bandwidth::from_mbps(9)));
+        assert_eq!(parse("1Gbps"), Ok(Bandwidth::from_gbps(1)));
         assert_eq!(
-            parse_bandwidth("16.1234mbps"),
-            Ok(Bandwidth::new(0, 16_123_400))
+            parse("18446744073709551615TBps"),
+            Err(Error::NumberOverflow)
         );
+
+        for (unit, scale) in [
+            ("Bps", 8),
+            ("B/s", 8),
+            ("KBps", 8_000),
+            ("kBps", 8_000),
+            ("KB/s", 8_000),
+            ("kB/s", 8_000),
+            ("MBps", 8_000_000),
+            ("MB/s", 8_000_000),
+            ("GBps", 8_000_000_000),
+            ("GB/s", 8_000_000_000),
+            ("TBps", 8_000_000_000_000),
+            ("TB/s", 8_000_000_000_000),
+        ] {
+            assert_eq!(
+                parse(&format!("125{}", unit)),
+                Ok(Bandwidth::from_bps(125 * scale)),
+                "{}",
+                unit
+            );
+            assert_eq!(
+                parse(&format!("0.5{}", unit)),
+                Ok(Bandwidth::from_bps(scale / 2)),
+                "{}",
+                unit
+            );
+        }
+        for (s, bps) in [
+            ("1.5Bps", 12),
+            ("0.125Bps", 1),
+            ("0.25B/s", 2),
+            ("2.75Bps", 22),
+            ("1.5KBps", 12_000),
+            ("0.001kBps", 8),
+            ("0.000125KB/s", 1),
+            ("12.345kB/s", 98_760),
+            ("1.5MBps", 12_000_000),
+            ("0.000001MBps", 8),
+            ("0.000000125MB/s", 1),
+            ("2.123456MBps", 16_987_648),
+            ("1.5GBps", 12_000_000_000),
+            ("0.5TBps", 4_000_000_000_000),
+        ] {
+            assert_eq!(parse(s), Ok(Bandwidth::from_bps(bps)), "{}", s);
+        }
+        // lowercase units are bits
+        assert_eq!(parse("1mbps"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(parse("1gb/s"), Ok(Bandwidth::from_gbps(1)));
+        // not the binary units
+        assert!(parse("1MiB/s").is_err());
+
+        let opts = ParseOptions::new().allow_byte_units(false);
+        let err = parse_bandwidth_with_options("1GBps", &opts).unwrap_err();
         assert_eq!(
-            parse_bandwidth("24.12345Mbit/s"),
-            Ok(Bandwidth::new(0, 24_123_450))
+            err,
+            Error::UnknownUnit {
+                start: 1,
+                end: 5,
+                unit: "GBps".into(),
+                value: 1,
+            }
         );
         assert_eq!(
-            parse_bandwidth("36.123456mbit/s"),
-            Ok(Bandwidth::new(0, 36_123_456))
+            err.to_string(),
+            "unknown bandwidth unit \"GBps\", \
+             byte rates are only accepted with ParseOptions::allow_byte_units"
         );
+    }
+
+    #[test]
+    fn test_bare_number_as_bps() {
+        let opts = ParseOptions::new().allow_bare_number_as_bps(true);
+        let parse = |s| parse_bandwidth_with_options(s, &opts);
+        assert_eq!(parse("1000"), Ok(Bandwidth::from_kbps(1)));
+        assert_eq!(parse(" 42 "), Ok(Bandwidth::from_bps(42)));
+        assert_eq!(parse("1Mbps 500"), Ok(Bandwidth::from_bps(1_000_500)));
+        assert_eq!(parse("1.9"), Ok(Bandwidth::from_bps(1)));
+        assert_eq!(parse("1Gbps"), Ok(Bandwidth::from_gbps(1)));
+        assert_eq!(parse(""), Err(Error::Empty));
+        assert!(matches!(
+            parse_bandwidth("1000"),
+            Err(Error::UnknownUnit {
+                start: 4,
+                end: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_relaxed() {
+        assert_eq!(parse_bandwidth_relaxed("0"), Ok(Bandwidth::ZERO));
         assert_eq!(
-            parse_bandwidth("48.123Mb/s"),
-            Ok(Bandwidth::new(0, 48_123_000))
+            parse_bandwidth_relaxed("1000000"),
+            Ok(Bandwidth::from_mbps(1))
         );
         assert_eq!(
-            parse_bandwidth("96.1234mb/s"),
-            Ok(Bandwidth::new(0, 96_123_400))
+            parse_bandwidth_relaxed("9999999999999"),
+            Ok(Bandwidth::new(9999, 999_999_999))
         );
         assert_eq!(
-            parse_bandwidth("2.123Gbps"),
-            Ok(Bandwidth::new(2, 123_000_000))
+            parse_bandwidth_relaxed("18446744073709551615"),
+            Ok(Bandwidth::from_bps(u64::MAX))
         );
         assert_eq!(
-            parse_bandwidth("4.1234gbps"),
-            Ok(Bandwidth::new(4, 123_400_000))
+            parse_bandwidth_relaxed("18446744073709551616"),
+            Err(Error::NumberOverflow)
         );
         assert_eq!(
-            parse_bandwidth("6.12345Gbit/s"),
-            Ok(Bandwidth::new(6, 123_450_000))
+            parse_bandwidth_relaxed("18446744073709551616Gbps"),
+            Err(Error::NumberOverflow)
         );
+        for s in ["9Tbps 420Gbps", "150.024kbps", "1MBps", "", "1Gbit", "bps"] {
+            assert_eq!(parse_bandwidth_relaxed(s), parse_bandwidth(s));
+        }
+    }
+
+    #[test]
+    fn test_max_fraction_digits() {
+        let opts = ParseOptions::new().max_fraction_digits(2);
+        let parse = |s| parse_bandwidth_with_options(s, &opts);
+        assert_eq!(parse("1.239Gbps"), Ok(Bandwidth::new(1, 230_000_000)));
+        assert_eq!(parse("1.2Gbps"), Ok(Bandwidth::new(1, 200_000_000)));
+        assert_eq!(parse("1.239Gbps 5bps"), Ok(Bandwidth::new(1, 230_000_005)));
+        let opts = ParseOptions::new().max_fraction_digits(0);
         assert_eq!(
-            parse_bandwidth("8.123456gbit/s"),
-            Ok(Bandwidth::new(8, 123_456_000))
+            parse_bandwidth_with_options("1.9Gbps", &opts),
+            Ok(Bandwidth::new(1, 0))
         );
         assert_eq!(
-            parse_bandwidth("16.123456789Gb/s"),
-            Ok(Bandwidth::new(16, 123_456_789))
+            ParseOptions::new().max_fraction_digits(12),
+            ParseOptions::new()
         );
+        // by default the 13th digit is ignored
         assert_eq!(
-            parse_bandwidth("40.12345678912gb/s"),
-            Ok(Bandwidth::new(40, 123_456_789))
+            parse_bandwidth("1.0000000000001Ebps"),
+            Ok(Bandwidth::new(1_000_000_000, 0))
         );
-        assert_eq!(parse_bandwidth("1.123Tbps"), Ok(Bandwidth::new(1_123, 0)));
         assert_eq!(
-            parse_bandwidth("2.1234tbps"),
-            Ok(Bandwidth::new(2_123, 400_000_000))
+            parse_bandwidth_with_options(
+                "1.000000000000000001Ebps",
+                &ParseOptions::new().max_fraction_digits(18)
+            ),
+            Ok(Bandwidth::new(1_000_000_000, 1))
         );
+
+        // the 13th digit of `Ebps` is 100kbps
+        let parse = |s, digits| {
+            parse_bandwidth_with_options(s, &ParseOptions::new().max_fraction_digits(digits))
+        };
+        let exa = Bandwidth::new(1_000_000_000, 0);
+        assert_eq!(parse("1.0000000000001Ebps", 12), Ok(exa));
         assert_eq!(
-            parse_bandwidth("4.12345Tbit/s"),
-            Ok(Bandwidth::new(4_123, 450_000_000))
+            parse("1.0000000000001Ebps", 13),
+            Ok(exa + Bandwidth::from_kbps(100))
         );
+        // below 1bps the extra digits make no difference
         assert_eq!(
-            parse_bandwidth("8.123456tbit/s"),
-            Ok(Bandwidth::new(8_123, 456_000_000))
+            parse("1.0000000000001Gbps", 13),
+            parse("1.0000000000001Gbps", 12)
         );
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 18 fraction digits are supported")]
+    fn test_max_fraction_digits_limit() {
+        let _ = ParseOptions::new().max_fraction_digits(19);
+    }
+
+    #[test]
+    fn test_max_spans() {
+        let opts = ParseOptions::new().max_spans(2);
+        let parse = |s| parse_bandwidth_with_options(s, &opts);
+        assert_eq!(parse("1Gbps"), Ok(Bandwidth::new(1, 0)));
+        assert_eq!(parse("1Gbps 5bps"), Ok(Bandwidth::new(1, 5)));
+        assert_eq!(parse("1Gbps 2Mbps 5bps"), Err(Error::TooManySpans(12)));
+        assert_eq!(parse("1Gbps2Mbps5bps"), Err(Error::TooManySpans(10)));
         assert_eq!(
-            parse_bandwidth("16.123456789Tb/s"),
-            Ok(Bandwidth::new(16_123, 456_789_000))
+            Error::TooManySpans(12).to_string(),
+            "too many rate spans at 12"
         );
+        let opts = ParseOptions::new().max_spans(0);
         assert_eq!(
-            parse_bandwidth("32.12345678912tb/s"),
-            Ok(Bandwidth::new(32_123, 456_789_120))
+            parse_bandwidth_with_options("1Gbps", &opts),
+            Err(Error::TooManySpans(0))
         );
     }
 
     #[test]
-    fn test_combo() {
+    fn test_rounding_mode() {
+        let floor = ParseOptions::new().rounding_mode(RoundingMode::Floor);
+        let round = ParseOptions::new().rounding_mode(RoundingMode::Round);
+        let ceil = ParseOptions::new().rounding_mode(RoundingMode::Ceil);
+        assert_eq!(ParseOptions::default(), floor);
+
+        assert_eq!(parse_bandwidth("1.9bps"), Ok(Bandwidth::from_bps(1)));
         assert_eq!(
-            parse_bandwidth("1bps 2bit/s 3b/s"),
-            Ok(Bandwidth::new(0, 6))
+            parse_bandwidth_with_options("1.9bps", &floor),
+            Ok(Bandwidth::from_bps(1))
         );
         assert_eq!(
-            parse_bandwidth("4kbps 5Kbps 6kbit/s"),
-            Ok(Bandwidth::new(0, 15_000))
+            parse_bandwidth_with_options("1.9bps", &round),
+            Ok(Bandwidth::from_bps(2))
         );
         assert_eq!(
-            parse_bandwidth("7Mbps 8mbps 9Mbit/s"),
-            Ok(Bandwidth::new(0, 24_000_000))
+            parse_bandwidth_with_options("1.9bps", &ceil),
+            Ok(Bandwidth::from_bps(2))
         );
+
         assert_eq!(
-            parse_bandwidth("10Gbps 11gbps 12Gbit/s"),
-            Ok(Bandwidth::new(33, 0))
+            parse_bandwidth_with_options("1.4bps", &round),
+            Ok(Bandwidth::from_bps(1))
         );
         assert_eq!(
-            parse_bandwidth("13Tbps 14tbps 15Tbit/s"),
-            Ok(Bandwidth::new(42_000, 0))
+            parse_bandwidth_with_options("1.5bps", &round),
+            Ok(Bandwidth::from_bps(2))
         );
         assert_eq!(
-            parse_bandwidth("10Gbps 5Mbps 1b/s"),
-            Ok(Bandwidth::new(10, 5_000_001))
+            parse_bandwidth_with_options("1.01bps", &ceil),
+            Ok(Bandwidth::from_bps(2))
         );
         assert_eq!(
-            parse_bandwidth("36Mbps 12kbps 24bps"),
-            Ok(Bandwidth::new(0, 36_012_024))
+            parse_bandwidth_with_options("1.0bps", &ceil),
+            Ok(Bandwidth::from_bps(1))
+        );
+        assert_eq!(
+            parse_bandwidth_with_options("150.0245kbps", &ceil),
+            Ok(Bandwidth::new(0, 150_025))
+        );
+        // rounding up may carry into the next gigabit
+        assert_eq!(
+            parse_bandwidth_with_options("1.9999999999Gbps", &round),
+            Ok(Bandwidth::new(2, 0))
+        );
+        assert_eq!(
+            parse_bandwidth_with_options("0.5Gbps 0.9999999999Gbps", &ceil),
+            Ok(Bandwidth::new(1, 500_000_000))
         );
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
-    fn test_decimal_combo() {
+    fn test_format_many_aligned() {
+        assert!(format_many_bandwidths_aligned(&[]).is_empty());
+        let vals = [
+            Bandwidth::new(0, 1),
+            Bandwidth::new(9420, 0),
+            Bandwidth::new(0, 32_000_000),
+        ];
+        let aligned = format_many_bandwidths_aligned(&vals);
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(aligned, ["    1bps", "9.42Tbps", "  32Mbps"]);
+        #[cfg(feature = "display-integer")]
+        assert_eq!(aligned, ["         1bps", "9Tbps 420Gbps", "       32Mbps"]);
+        for (s, val) in aligned.iter().zip(vals) {
+            assert_eq!(parse_bandwidth(s), Ok(val));
+        }
+    }
+
+    #[test]
+    fn test_pps() {
+        let opts = ParseOptions::new().pps_packet_size_bytes(1500);
         assert_eq!(
-            parse_bandwidth("1.1bps 2.2bit/s 3.3b/s"),
-            Ok(Bandwidth::new(0, 6))
+            parse_bandwidth_with_options("1Mpps", &opts),
+            Ok(Bandwidth::from_gbps(12))
         );
         assert_eq!(
-            parse_bandwidth("4.4kbps 5.5Kbps 6.6kbit/s"),
-            Ok(Bandwidth::new(0, 16_500))
+            parse_bandwidth_with_options("1.5kpps", &opts),
+            Ok(Bandwidth::from_mbps(18))
         );
         assert_eq!(
-            parse_bandwidth("7.7Mbps 8.8mbps 9.9Mbit/s"),
-            Ok(Bandwidth::new(0, 26_400_000))
+            parse_bandwidth_with_options("10pps 1Mbps", &opts),
+            Ok(Bandwidth::from_bps(1_120_000))
         );
+        // fractions of a packet are kept
         assert_eq!(
-            parse_bandwidth("10.10Gbps 11.11gbps 12.12Gbit/s"),
-            Ok(Bandwidth::new(33, 330_000_000))
+            parse_bandwidth_with_options("1.9pps", &opts),
+            Ok(Bandwidth::from_bps(22_800))
         );
+        let opts = ParseOptions::new().pps_packet_size_bytes(1000);
         assert_eq!(
-            parse_bandwidth("13.13Tbps 14.14tbps 15.15Tbit/s"),
-            Ok(Bandwidth::new(42_420, 0))
+            parse_bandwidth_with_options("1.5pps", &opts),
+            Ok(Bandwidth::from_bps(12_000))
         );
         assert_eq!(
-            parse_bandwidth("10.1Gbps 5.2Mbps 1.3b/s"),
-            Ok(Bandwidth::new(10, 105_200_001))
+            parse_bandwidth_with_options("0.0001pps", &opts),
+            Ok(Bandwidth::ZERO)
         );
         assert_eq!(
-            parse_bandwidth("36.1Mbps 12.2kbps 24.3bps"),
-            Ok(Bandwidth::new(0, 36_112_224))
+            parse_bandwidth_with_options("1.0005kpps", &opts),
+            Ok(Bandwidth::from_bps(8_004_000))
+        );
+        let opts = opts.rounding_mode(RoundingMode::Ceil);
+        assert_eq!(
+            parse_bandwidth_with_options("0.0001pps", &opts),
+            Ok(Bandwidth::from_bps(1))
+        );
+        let opts = ParseOptions::new().pps_packet_size_bytes(64);
+        assert_eq!(
+            parse_bandwidth_with_options("14.88Mpps", &opts),
+            Ok(Bandwidth::new(7, 618_560_000))
+        );
+        let opts = ParseOptions::new().pps_packet_size_bytes(u16::MAX);
+        assert_eq!(
+            parse_bandwidth_with_options("18446744073709551615Mpps", &opts),
+            Err(Error::NumberOverflow)
+        );
+        assert_eq!(
+            parse_bandwidth("1Mpps"),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 5,
+                unit: "Mpps".into(),
+                value: 1,
+            })
+        );
+
+        let fmt = |bw, size| format_bandwidth(bw).with_packet_size(size).to_string();
+        assert_eq!(fmt(Bandwidth::from_gbps(12), 1500), "1Mpps");
+        assert_eq!(fmt(Bandwidth::from_mbps(18), 1500), "1.5kpps");
+        assert_eq!(fmt(Bandwidth::new(7, 618_560_000), 64), "14.88Mpps");
+        assert_eq!(fmt(Bandwidth::from_bps(12_000), 1500), "1pps");
+        assert_eq!(fmt(Bandwidth::from_bps(11_999), 1500), "0.999pps");
+        assert_eq!(fmt(Bandwidth::from_bps(18_000), 1500), "1.5pps");
+        assert_eq!(fmt(Bandwidth::from_bps(100), 1500), "0.008pps");
+        assert_eq!(fmt(Bandwidth::from_bps(6_000), 1500), "0.5pps");
+        assert_eq!(fmt(Bandwidth::from_bps(11), 1500), "0pps");
+        assert_eq!(fmt(Bandwidth::ZERO, 1500), "0pps");
+        assert_eq!(fmt(Bandwidth::MAX, 1), "2305843009213693951999.999Mpps");
+        assert_eq!(fmt(Bandwidth::from_bps(1_234_567_000), 1), "154.32Mpps");
+    }
+
+    #[test]
+    #[should_panic(expected = "packet size must be non-zero")]
+    fn test_pps_zero_packet_size() {
+        ParseOptions::new().pps_packet_size_bytes(0);
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let opts = ParseOptions::new().allow_unlimited(true);
+        for s in [
+            "unlimited",
+            "inf",
+            "infinity",
+            "∞",
+            "max",
+            " Unlimited ",
+            "INF",
+        ] {
+            assert_eq!(parse_bandwidth_with_options(s, &opts), Ok(Bandwidth::MAX));
+        }
+        assert_eq!(
+            parse_bandwidth_with_options("1Gbps", &opts),
+            Ok(Bandwidth::new(1, 0))
+        );
+        assert!(parse_bandwidth_with_options("unlimitedbps", &opts).is_err());
+        assert!(parse_bandwidth("unlimited").is_err());
+        assert!(parse_bandwidth_with_options("∞", &ParseOptions::new()).is_err());
+
+        let sentinel = parse_bandwidth_with_options("unlimited", &opts).unwrap();
+        assert_eq!(
+            format_bandwidth_or_unlimited(sentinel, Bandwidth::MAX).to_string(),
+            "unlimited"
+        );
+        assert_eq!(
+            format_bandwidth_or_unlimited(Bandwidth::new(1, 0), Bandwidth::MAX).to_string(),
+            "1Gbps"
+        );
+        assert_eq!(
+            format_bandwidth_or_unlimited(Bandwidth::ZERO, Bandwidth::ZERO).to_string(),
+            "unlimited"
         );
     }
 
@@ -728,7 +4400,26 @@ mod tests {
         assert_eq!(
             parse_bandwidth("10 byte/s").unwrap_err().to_string(),
             "unknown bandwidth unit \"byte/s\", \
-                    supported units: bps, kbps, Mbps, Gbps, Tbps"
+                    supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps, \
+                    Bps, KBps, MBps, GBps, TBps"
+        );
+        #[cfg(all(feature = "alloc", not(feature = "no-suggestions")))]
+        {
+            assert_eq!(
+                parse_bandwidth("1Gbps/s").unwrap_err().to_string(),
+                "unknown bandwidth unit \"Gbps/s\", did you mean \"Gbps\"?"
+            );
+            assert_eq!(
+                parse_bandwidth("1Gbit").unwrap_err().to_string(),
+                "unknown bandwidth unit \"Gbit\", did you mean \"Gbit/s\"?"
+            );
+        }
+        #[cfg(any(not(feature = "alloc"), feature = "no-suggestions"))]
+        assert_eq!(
+            parse_bandwidth("1Gbps/s").unwrap_err().to_string(),
+            "unknown bandwidth unit \"Gbps/s\", \
+                    supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps, \
+                    Bps, KBps, MBps, GBps, TBps"
         );
     }
 
@@ -795,6 +4486,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decimal_trailing_zeros() {
+        let groups = [0, 1, 5, 9, 10, 50, 90, 100, 120, 500, 900, 909, 990, 999];
+        for &mbps in &[1, 10, 100, 410, 999] {
+            for &kbps in &groups {
+                for &bps in &groups {
+                    let bw = Bandwidth::new(0, mbps * 1_000_000 + kbps * 1_000 + bps);
+                    let digits = format!("{:03}{:03}", kbps, bps);
+                    let digits = digits.trim_end_matches('0');
+                    let expected = if digits.is_empty() {
+                        format!("{}Mbps", mbps)
+                    } else {
+                        format!("{}.{}Mbps", mbps, digits)
+                    };
+                    let val = format_bandwidth_with_options(bw, &FormatOptions::decimal());
+                    assert_eq!(val.to_string(), expected);
+                }
+            }
+        }
+        assert_eq!(
+            format_bandwidth_with_options(
+                Bandwidth::new(0, 410_008_900),
+                &FormatOptions::decimal()
+            )
+            .to_string(),
+            "410.0089Mbps"
+        );
+    }
+
     #[test]
     fn test_formatted_bandwidth_decimal() {
         struct TestDecimal(FormattedBandwidth);
@@ -860,5 +4580,152 @@ mod tests {
             "9.42Tbps",
             TestDecimal::from(format_bandwidth(Bandwidth::new(9420, 0))).to_string(),
         );
+        // every component is non-zero
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(1001, 1_001_001))).to_string(),
+            "1.001001001001Tbps"
+        );
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(999, 999_999_999))).to_string(),
+            "999.999999999Gbps"
+        );
+        // alternating zero and non-zero components
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(1000, 1_000_001))).to_string(),
+            "1.000001000001Tbps"
+        );
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(1, 1_000))).to_string(),
+            "1.000001Gbps"
+        );
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(0, 10_010_010))).to_string(),
+            "10.01001Mbps"
+        );
+        // bps is the only non-zero sub-Gbps component
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(5, 7))).to_string(),
+            "5.000000007Gbps"
+        );
+        assert_eq!(
+            TestDecimal::from(format_bandwidth(Bandwidth::new(2000, 100))).to_string(),
+            "2.0000000001Tbps"
+        );
+    }
+
+    #[test]
+    fn test_significant_unit() {
+        let unit = |gbps, bps| format_bandwidth(Bandwidth::new(gbps, bps)).significant_unit();
+        assert_eq!(unit(0, 0), BandwidthUnit::Bps);
+        assert_eq!(unit(0, 500), BandwidthUnit::Bps);
+        assert_eq!(unit(0, 999), BandwidthUnit::Bps);
+        assert_eq!(unit(0, 1_000), BandwidthUnit::Kbps);
+        assert_eq!(unit(0, 999_999), BandwidthUnit::Kbps);
+        assert_eq!(unit(0, 1_000_000), BandwidthUnit::Mbps);
+        assert_eq!(unit(0, 999_999_999), BandwidthUnit::Mbps);
+        assert_eq!(unit(1, 0), BandwidthUnit::Gbps);
+        assert_eq!(unit(999, 999_999_999), BandwidthUnit::Gbps);
+        assert_eq!(unit(1_000, 0), BandwidthUnit::Tbps);
+        assert_eq!(unit(9420, 5), BandwidthUnit::Tbps);
+        assert_eq!(unit(999_999, 999_999_999), BandwidthUnit::Tbps);
+        assert_eq!(unit(1_000_000, 0), BandwidthUnit::Pbps);
+        assert_eq!(unit(999_999_999, 0), BandwidthUnit::Pbps);
+        assert_eq!(unit(1_000_000_000, 0), BandwidthUnit::Ebps);
+        assert_eq!(unit(u64::MAX, 999_999_999), BandwidthUnit::Ebps);
+    }
+
+    #[test]
+    fn test_value_in_unit() {
+        let val = format_bandwidth(Bandwidth::new(9420, 5));
+        assert_eq!(val.value_in_unit(BandwidthUnit::Tbps), 9.420000000005);
+        assert_eq!(val.value_in_unit(BandwidthUnit::Gbps), 9420.000000005);
+        assert_eq!(val.value_in_unit(BandwidthUnit::Bps), 9_420_000_000_005.0);
+        let val = format_bandwidth(Bandwidth::new(0, 500));
+        assert_eq!(val.value_in_unit(BandwidthUnit::Bps), 500.0);
+        assert_eq!(val.value_in_unit(BandwidthUnit::Kbps), 0.5);
+        assert_eq!(val.value_in_unit(BandwidthUnit::Mbps), 0.0005);
+        let val = format_bandwidth(Bandwidth::new(0, 0));
+        assert_eq!(val.value_in_unit(BandwidthUnit::Gbps), 0.0);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn test_format_bandwidth_array() {
+        for val in [
+            Bandwidth::new(0, 0),
+            Bandwidth::new(0, 1),
+            Bandwidth::new(0, 51_200),
+            Bandwidth::new(0, 100_060_007),
+            Bandwidth::new(4, 500_000_000),
+            Bandwidth::new(8700, 32_000_000),
+            Bandwidth::new(999, 999_999_999),
+        ] {
+            assert_eq!(
+                format_bandwidth_array(val).as_str(),
+                format_bandwidth(val).to_string()
+            );
+        }
+
+        let truncated = format_bandwidth_array(Bandwidth::MAX);
+        assert!(truncated.ends_with('…'));
+        assert!(format_bandwidth(Bandwidth::MAX)
+            .to_string()
+            .starts_with(truncated.trim_end_matches('…')));
+    }
+
+    #[test]
+    fn test_humantime_transfer() {
+        use std::time::Duration;
+
+        let bw = parse_bandwidth("100Mbps").unwrap();
+        let dur = humantime::parse_duration("1min 30s").unwrap();
+        assert_eq!(dur, Duration::from_secs(90));
+        let bytes = bw.as_bps() * dur.as_nanos() / 8_000_000_000;
+        assert_eq!(bytes, 1_125_000_000);
+
+        // bytes/s back into a bandwidth over the same duration
+        let rate = Bandwidth::from_bps(bytes as u64 * 8 / dur.as_secs());
+        assert_eq!(rate, bw);
+        let summary = format!(
+            "{} for {}",
+            format_bandwidth_with_options(rate, &FormatOptions::decimal()),
+            humantime::format_duration(dur)
+        );
+        assert_eq!(summary, "100Mbps for 1m 30s");
+
+        // both parsers accept the other's output for a combined setting
+        let (bw, tail) = parse_bandwidth_partial("1.5Gbps 2h").unwrap();
+        assert_eq!(bw, Bandwidth::new(1, 500_000_000));
+        assert_eq!(
+            humantime::parse_duration(tail.trim()),
+            Ok(Duration::from_secs(7200))
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_within_1bps() {
+        // large values need all 18 fraction digits of `Ebps`
+        let full = ParseOptions::new().max_fraction_digits(18);
+        for bw in [
+            Bandwidth::ZERO,
+            Bandwidth::new(0, 1),
+            Bandwidth::new(0, 999),
+            Bandwidth::new(0, 150_024),
+            Bandwidth::new(1, 500_000_000),
+            Bandwidth::new(9420, 5),
+            Bandwidth::new(123_456, 789_012_345),
+            Bandwidth::new(u64::MAX / 1000, 999_999_999),
+        ] {
+            for opts in [FormatOptions::decimal(), FormatOptions::integer()] {
+                let s = format_bandwidth_with_options(bw, &opts).to_string();
+                let parsed = parse_bandwidth_with_options(&s, &full).unwrap();
+                let diff = if parsed > bw {
+                    parsed - bw
+                } else {
+                    bw - parsed
+                };
+                assert!(diff <= Bandwidth::new(0, 1), "{} -> {:?}", s, parsed);
+            }
+        }
     }
 }
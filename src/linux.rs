@@ -0,0 +1,84 @@
+//! Helpers for network interface speeds reported by Linux
+//!
+//! Linux reports the speed of a network interface in Mbps, both through
+//! `/sys/class/net/<iface>/speed` and the `SIOCETHTOOL` ioctl.
+//!
+//! This module is available on Linux, or on any target with the
+//! `linux-compat` feature enabled.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::linux::from_network_interface_speed;
+//!
+//! assert_eq!(from_network_interface_speed(10000), Some(Bandwidth::new(10, 0)));
+//! // The speed is unknown, e.g. the link is down
+//! assert_eq!(from_network_interface_speed(-1), None);
+//! ```
+
+use bandwidth::Bandwidth;
+
+/// Converts the signed speed in Mbps reported by sysfs into a `Bandwidth`
+///
+/// Linux uses `-1` to indicate that the speed is unknown, for example
+/// because the interface is not connected. `None` is returned for any
+/// negative value.
+pub fn from_network_interface_speed(speed_mbit: i64) -> Option<Bandwidth> {
+    if speed_mbit < 0 {
+        None
+    } else {
+        Some(Bandwidth::from_mbps(speed_mbit as u64))
+    }
+}
+
+/// Converts the unsigned speed in Mbps of an `ethtool_cmd` into a `Bandwidth`
+///
+/// Note that the kernel reports an unknown speed as `SPEED_UNKNOWN`, which is
+/// `u32::MAX` in this form. Callers should check for it before converting.
+pub fn from_ethtool_speed(speed_mbit: u32) -> Bandwidth {
+    Bandwidth::from_mbps(speed_mbit as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BandwidthExt;
+
+    #[test]
+    fn test_network_interface_speed() {
+        assert_eq!(Bandwidth::from_network_interface_speed(-1), None);
+        assert_eq!(
+            Bandwidth::from_network_interface_speed(0),
+            Some(Bandwidth::new(0, 0))
+        );
+        assert_eq!(
+            Bandwidth::from_network_interface_speed(1000),
+            Some(Bandwidth::new(1, 0))
+        );
+        assert_eq!(
+            Bandwidth::from_network_interface_speed(25000),
+            Some(Bandwidth::new(25, 0))
+        );
+        assert_eq!(
+            Bandwidth::from_network_interface_speed(100000),
+            Some(Bandwidth::new(100, 0))
+        );
+        assert_eq!(Bandwidth::from_network_interface_speed(i64::MIN), None);
+    }
+
+    #[test]
+    fn test_ethtool_speed() {
+        assert_eq!(Bandwidth::from_ethtool_speed(0), Bandwidth::new(0, 0));
+        assert_eq!(
+            Bandwidth::from_ethtool_speed(10),
+            Bandwidth::new(0, 10_000_000)
+        );
+        assert_eq!(Bandwidth::from_ethtool_speed(1000), Bandwidth::new(1, 0));
+        assert_eq!(Bandwidth::from_ethtool_speed(25000), Bandwidth::new(25, 0));
+        assert_eq!(
+            Bandwidth::from_ethtool_speed(100000),
+            Bandwidth::new(100, 0)
+        );
+    }
+}
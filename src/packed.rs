@@ -0,0 +1,193 @@
+//! Fixed-size bandwidth encoding
+
+use bandwidth::Bandwidth;
+
+/// A `Bandwidth` packed in exactly 8 bytes as a raw number of bits per second
+///
+/// Unlike `Bandwidth`, whose internal representation is not stable, this type
+/// is suitable for network protocols and on-disk formats. `u64::MAX` is
+/// reserved as a sentinel for an unlimited bandwidth, see
+/// [`PackedBandwidth::UNLIMITED`].
+///
+/// Only available with the `packed` feature. With the `serde` feature, it
+/// serializes as a human-readable string, or `"unlimited"` for the sentinel.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::PackedBandwidth;
+///
+/// let packed = PackedBandwidth::from_bandwidth(Bandwidth::new(1, 5)).unwrap();
+/// assert_eq!(packed.to_bytes(), 1_000_000_005u64.to_le_bytes());
+/// assert_eq!(packed.to_bandwidth(), Some(Bandwidth::new(1, 5)));
+/// assert_eq!(PackedBandwidth::UNLIMITED.to_bandwidth(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct PackedBandwidth(u64);
+
+impl PackedBandwidth {
+    /// The sentinel value for an unlimited (infinite) bandwidth
+    pub const UNLIMITED: PackedBandwidth = PackedBandwidth(u64::MAX);
+
+    /// Packs a `Bandwidth`
+    ///
+    /// Returns `None` if the number of bits per second does not fit in a
+    /// `u64`, or collides with the [`UNLIMITED`][Self::UNLIMITED] sentinel.
+    pub fn from_bandwidth(bw: Bandwidth) -> Option<PackedBandwidth> {
+        match u64::try_from(bw.as_bps()) {
+            Ok(bps) if bps != u64::MAX => Some(PackedBandwidth(bps)),
+            _ => None,
+        }
+    }
+
+    /// Unpacks the `Bandwidth`, returning `None` for the
+    /// [`UNLIMITED`][Self::UNLIMITED] sentinel
+    pub fn to_bandwidth(self) -> Option<Bandwidth> {
+        if self.is_unlimited() {
+            None
+        } else {
+            Some(Bandwidth::from_bps(self.0))
+        }
+    }
+
+    /// Returns `true` if this is the [`UNLIMITED`][Self::UNLIMITED] sentinel
+    pub fn is_unlimited(self) -> bool {
+        self == Self::UNLIMITED
+    }
+
+    /// Decodes a `PackedBandwidth` from its little-endian bytes
+    pub fn from_bytes(bytes: [u8; 8]) -> PackedBandwidth {
+        PackedBandwidth(u64::from_le_bytes(bytes))
+    }
+
+    /// Encodes this `PackedBandwidth` as little-endian bytes
+    pub fn to_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::PackedBandwidth;
+    use crate::serde::Serde;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    const UNLIMITED: &str = "unlimited";
+
+    impl Serialize for PackedBandwidth {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match self.to_bandwidth() {
+                Some(bw) => Serde::from(bw).serialize(serializer),
+                None => serializer.serialize_str(UNLIMITED),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PackedBandwidth {
+        fn deserialize<D>(d: D) -> Result<PackedBandwidth, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct V;
+
+            impl de::Visitor<'_> for V {
+                type Value = PackedBandwidth;
+
+                fn expecting(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    fmt.write_str("a bandwidth below u64::MAX bps or \"unlimited\"")
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<PackedBandwidth, E>
+                where
+                    E: de::Error,
+                {
+                    if v == UNLIMITED {
+                        return Ok(PackedBandwidth::UNLIMITED);
+                    }
+                    crate::parse_bandwidth(v)
+                        .ok()
+                        .and_then(PackedBandwidth::from_bandwidth)
+                        .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+                }
+            }
+
+            d.deserialize_str(V)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentinel() {
+        assert!(PackedBandwidth::UNLIMITED.is_unlimited());
+        assert_eq!(PackedBandwidth::UNLIMITED.to_bandwidth(), None);
+        assert_eq!(PackedBandwidth::UNLIMITED.to_bytes(), [0xff; 8]);
+        assert_eq!(
+            PackedBandwidth::from_bytes([0xff; 8]),
+            PackedBandwidth::UNLIMITED
+        );
+        assert_eq!(
+            PackedBandwidth::from_bandwidth(Bandwidth::from_bps(u64::MAX)),
+            None
+        );
+        assert_eq!(PackedBandwidth::from_bandwidth(Bandwidth::MAX), None);
+    }
+
+    #[test]
+    fn test_zero() {
+        let zero = PackedBandwidth::from_bandwidth(Bandwidth::ZERO).unwrap();
+        assert!(!zero.is_unlimited());
+        assert_eq!(zero.to_bytes(), [0; 8]);
+        assert_eq!(zero.to_bandwidth(), Some(Bandwidth::ZERO));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for bw in [
+            Bandwidth::from_bps(1),
+            Bandwidth::new(1, 500_000_000),
+            Bandwidth::new(400, 0),
+            Bandwidth::from_bps(u64::MAX - 1),
+        ] {
+            let packed = PackedBandwidth::from_bandwidth(bw).unwrap();
+            assert_eq!(PackedBandwidth::from_bytes(packed.to_bytes()), packed);
+            assert_eq!(packed.to_bandwidth(), Some(bw));
+        }
+        assert_eq!(
+            PackedBandwidth::from_bandwidth(Bandwidth::new(0, 0x0102))
+                .unwrap()
+                .to_bytes(),
+            [0x02, 0x01, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let packed = PackedBandwidth::from_bandwidth(Bandwidth::from_mbps(15)).unwrap();
+        let json = serde_json::to_string(&packed).unwrap();
+        assert_eq!(json, r#""15Mbps""#);
+        assert_eq!(
+            serde_json::from_str::<PackedBandwidth>(&json).unwrap(),
+            packed
+        );
+
+        let json = serde_json::to_string(&PackedBandwidth::UNLIMITED).unwrap();
+        assert_eq!(json, r#""unlimited""#);
+        assert_eq!(
+            serde_json::from_str::<PackedBandwidth>(&json).unwrap(),
+            PackedBandwidth::UNLIMITED
+        );
+
+        assert!(serde_json::from_str::<PackedBandwidth>(r#""20000000Tbps""#).is_err());
+        assert!(serde_json::from_str::<PackedBandwidth>(r#""1 byte/s""#).is_err());
+    }
+}
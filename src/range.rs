@@ -0,0 +1,813 @@
+//! Bandwidth ranges
+
+use crate::{format_bandwidth, pad, parse_bandwidth, Error};
+use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+/// An inclusive range of bandwidth values, from `min` to `max`
+///
+/// A range without an upper bound ends at [`Bandwidth::MAX`], the sentinel
+/// for an unlimited bandwidth, and one without a lower bound starts at zero.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::BandwidthRange;
+///
+/// let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+/// assert_eq!(range.min(), Bandwidth::from_mbps(100));
+/// assert_eq!(range.max(), Bandwidth::from_gbps(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BandwidthRange {
+    min: Bandwidth,
+    max: Bandwidth,
+}
+
+impl BandwidthRange {
+    /// Creates a new range from `min` to `max` (inclusive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn new(min: Bandwidth, max: Bandwidth) -> BandwidthRange {
+        assert!(min <= max, "range minimum is greater than its maximum");
+        BandwidthRange { min, max }
+    }
+
+    /// Creates a range from `min` without an upper bound, displayed as
+    /// `100Mbps..`
+    pub fn unbounded_above(min: Bandwidth) -> BandwidthRange {
+        BandwidthRange::new(min, Bandwidth::MAX)
+    }
+
+    /// Creates a range up to `max` without a lower bound, displayed as
+    /// `..1Gbps`
+    pub fn unbounded_below(max: Bandwidth) -> BandwidthRange {
+        BandwidthRange::new(Bandwidth::ZERO, max)
+    }
+
+    /// Returns the lower bound of the range
+    pub fn min(&self) -> Bandwidth {
+        self.min
+    }
+
+    /// Returns the upper bound of the range
+    pub fn max(&self) -> Bandwidth {
+        self.max
+    }
+
+    /// Returns `true` if `bw` lies within the range, bounds included
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthRange;
+    ///
+    /// let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+    /// assert!(range.contains(Bandwidth::from_gbps(1)));
+    /// assert!(!range.contains(Bandwidth::from_mbps(10)));
+    /// ```
+    pub fn contains(&self, bw: Bandwidth) -> bool {
+        self.min <= bw && bw <= self.max
+    }
+
+    /// Returns the value of the range closest to `bw`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthRange;
+    ///
+    /// let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+    /// assert_eq!(range.clamp(Bandwidth::from_gbps(10)), Bandwidth::from_gbps(1));
+    /// assert_eq!(range.clamp(Bandwidth::from_mbps(500)), Bandwidth::from_mbps(500));
+    /// ```
+    pub fn clamp(&self, bw: Bandwidth) -> Bandwidth {
+        bw.clamp(self.min, self.max)
+    }
+
+    /// Returns an iterator over `min, min + step, min + 2 * step, ...`
+    ///
+    /// The iterator ends with the last value not greater than `max`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthRange;
+    ///
+    /// let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_mbps(350));
+    /// let steps: Vec<_> = range.step_by(Bandwidth::from_mbps(100)).collect();
+    /// assert_eq!(
+    ///     steps,
+    ///     [
+    ///         Bandwidth::from_mbps(100),
+    ///         Bandwidth::from_mbps(200),
+    ///         Bandwidth::from_mbps(300),
+    ///     ]
+    /// );
+    /// ```
+    pub fn step_by(&self, step: Bandwidth) -> impl Iterator<Item = Bandwidth> {
+        assert!(!step.is_zero(), "step must be non-zero");
+        let max = self.max;
+        core::iter::successors(Some(self.min), move |bw| bw.checked_add(step))
+            .take_while(move |bw| *bw <= max)
+    }
+
+    /// Returns an iterator over `min, min * ratio, min * ratio^2, ...`
+    ///
+    /// The iterator ends with the last value not greater than `max`, or
+    /// once multiplying no longer makes the value grow (e.g. when `min` is
+    /// zero).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not greater than `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::BandwidthRange;
+    ///
+    /// let range = BandwidthRange::new(Bandwidth::from_mbps(1), Bandwidth::from_gbps(1));
+    /// let steps: Vec<_> = range.geometric_step_by(10.0).collect();
+    /// assert_eq!(
+    ///     steps,
+    ///     [
+    ///         Bandwidth::from_mbps(1),
+    ///         Bandwidth::from_mbps(10),
+    ///         Bandwidth::from_mbps(100),
+    ///         Bandwidth::from_gbps(1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn geometric_step_by(&self, ratio: f64) -> impl Iterator<Item = Bandwidth> {
+        assert!(ratio > 1.0, "ratio must be greater than 1");
+        let max = self.max;
+        core::iter::successors(Some(self.min), move |bw| {
+            Bandwidth::try_from_gbps_f64(bw.as_gbps_f64() * ratio)
+                .ok()
+                .filter(|next| next > bw)
+        })
+        .take_while(move |bw| *bw <= max)
+    }
+}
+
+/// Displays the range like a Rust range literal, e.g. `100Mbps..1Gbps`
+///
+/// Each bound is formatted with [`format_bandwidth`], so the output can be
+/// parsed back with [`parse_bandwidth_range`]. A lower bound of zero and an
+/// upper bound of [`Bandwidth::MAX`] are left out, as in `100Mbps..`.
+///
+/// The precision of the formatter applies to each bound, while the width
+/// and fill pad the range as a whole, right-aligning by default.
+impl fmt::Display for BandwidthRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad(
+            f,
+            &Bounds {
+                range: self,
+                precision: f.precision(),
+            },
+        )
+    }
+}
+
+/// The bounds of a [`BandwidthRange`], formatted with a precision but
+/// without padding
+struct Bounds<'a> {
+    range: &'a BandwidthRange,
+    precision: Option<usize>,
+}
+
+impl fmt::Display for Bounds<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bound = |f: &mut fmt::Formatter, bw| match self.precision {
+            Some(precision) => write!(f, "{:.*}", precision, format_bandwidth(bw)),
+            None => write!(f, "{}", format_bandwidth(bw)),
+        };
+        if !self.range.min.is_zero() {
+            bound(f, self.range.min)?;
+        }
+        f.write_str("..")?;
+        if self.range.max != Bandwidth::MAX {
+            bound(f, self.range.max)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses with [`parse_bandwidth_range`]
+impl FromStr for BandwidthRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<BandwidthRange, Error> {
+        parse_bandwidth_range(s)
+    }
+}
+
+/// A wrapper for bandwidth ranges, analogous to
+/// [`HumanBandwidth`][crate::HumanBandwidth]
+///
+/// It parses and displays like [`BandwidthRange`] and dereferences to it,
+/// which is convenient for argument parsers working with `FromStr` types.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::HumanBandwidthRange;
+///
+/// let x: HumanBandwidthRange = "100Mbps - 1Gbps".parse().unwrap();
+/// assert_eq!(x.max(), Bandwidth::from_gbps(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HumanBandwidthRange(BandwidthRange);
+
+impl AsRef<BandwidthRange> for HumanBandwidthRange {
+    fn as_ref(&self) -> &BandwidthRange {
+        &self.0
+    }
+}
+
+impl Deref for HumanBandwidthRange {
+    type Target = BandwidthRange;
+
+    fn deref(&self) -> &BandwidthRange {
+        &self.0
+    }
+}
+
+impl From<HumanBandwidthRange> for BandwidthRange {
+    fn from(val: HumanBandwidthRange) -> BandwidthRange {
+        val.0
+    }
+}
+
+impl From<BandwidthRange> for HumanBandwidthRange {
+    fn from(val: BandwidthRange) -> HumanBandwidthRange {
+        HumanBandwidthRange(val)
+    }
+}
+
+impl FromStr for HumanBandwidthRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<HumanBandwidthRange, Error> {
+        parse_bandwidth_range(s).map(HumanBandwidthRange)
+    }
+}
+
+impl fmt::Display for HumanBandwidthRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+fn shift_error(e: Error, off: usize) -> Error {
+    match e {
+        Error::InvalidCharacter(pos) => Error::InvalidCharacter(pos + off),
+        Error::NumberExpected(pos) => Error::NumberExpected(pos + off),
+        Error::UnknownUnit {
+            start,
+            end,
+            unit,
+            value,
+        } => Error::UnknownUnit {
+            start: start + off,
+            end: end + off,
+            unit,
+            value,
+        },
+        e => e,
+    }
+}
+
+/// Parse bandwidth range `100Mbps..1Gbps` or `100Mbps-1Gbps`
+///
+/// Each bound is parsed with [`parse_bandwidth`], and whitespace around the
+/// separator is allowed. With the `..` separator either bound may be left
+/// out, as in `100Mbps..` (see [`BandwidthRange::unbounded_above`]) or
+/// `..1Gbps`. Error offsets are relative to the whole string.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_bandwidth_range, BandwidthRange, Error};
+///
+/// let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+/// assert_eq!(parse_bandwidth_range("100Mbps..1Gbps"), Ok(range));
+/// assert_eq!(parse_bandwidth_range("100Mbps - 1Gbps"), Ok(range));
+/// assert_eq!(
+///     parse_bandwidth_range("100Mbps.."),
+///     Ok(BandwidthRange::unbounded_above(Bandwidth::from_mbps(100)))
+/// );
+/// assert_eq!(parse_bandwidth_range("1Gbps-100Mbps"), Err(Error::InvalidRange));
+/// ```
+pub fn parse_bandwidth_range(s: &str) -> Result<BandwidthRange, Error> {
+    let bound = |s: &str, off: usize, unbounded: Bandwidth| {
+        if s.trim().is_empty() {
+            Ok(unbounded)
+        } else {
+            parse_bandwidth(s).map_err(|e| shift_error(e, off))
+        }
+    };
+    let (min, max) = match s.find("..") {
+        Some(i) => (
+            bound(&s[..i], 0, Bandwidth::ZERO)?,
+            bound(&s[i + 2..], i + 2, Bandwidth::MAX)?,
+        ),
+        None => {
+            let (min, max) = s.split_once('-').ok_or(Error::InvalidRange)?;
+            let off = min.len() + 1;
+            let min = parse_bandwidth(min)?;
+            (min, parse_bandwidth(max).map_err(|e| shift_error(e, off))?)
+        }
+    };
+    if min > max {
+        return Err(Error::InvalidRange);
+    }
+    Ok(BandwidthRange { min, max })
+}
+
+/// Error returned by [`parse_bandwidth_checked`]
+#[derive(Debug, PartialEq, Clone)]
+pub enum BandwidthCheckError {
+    /// The input could not be parsed
+    Parse(Error),
+    /// The input was parsed, but lies outside of the allowed range
+    OutOfRange {
+        /// The parsed bandwidth
+        parsed: Bandwidth,
+        /// The allowed range
+        range: BandwidthRange,
+    },
+}
+
+#[cfg(feature = "std")]
+impl StdError for BandwidthCheckError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            BandwidthCheckError::Parse(e) => Some(e),
+            BandwidthCheckError::OutOfRange { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for BandwidthCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BandwidthCheckError::Parse(e) => e.fmt(f),
+            BandwidthCheckError::OutOfRange { parsed, range } => write!(
+                f,
+                "bandwidth {} is out of range {}",
+                format_bandwidth(*parsed),
+                range
+            ),
+        }
+    }
+}
+
+impl From<Error> for BandwidthCheckError {
+    fn from(e: Error) -> BandwidthCheckError {
+        BandwidthCheckError::Parse(e)
+    }
+}
+
+/// Parse bandwidth with [`parse_bandwidth`] and check that it lies within
+/// `range`
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_bandwidth_checked, BandwidthCheckError, BandwidthRange};
+///
+/// let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+/// assert_eq!(parse_bandwidth_checked("500Mbps", &range), Ok(Bandwidth::from_mbps(500)));
+/// assert_eq!(
+///     parse_bandwidth_checked("10Gbps", &range),
+///     Err(BandwidthCheckError::OutOfRange {
+///         parsed: Bandwidth::from_gbps(10),
+///         range,
+///     })
+/// );
+/// ```
+pub fn parse_bandwidth_checked(
+    s: &str,
+    range: &BandwidthRange,
+) -> Result<Bandwidth, BandwidthCheckError> {
+    let parsed = parse_bandwidth(s)?;
+    if !range.contains(parsed) {
+        return Err(BandwidthCheckError::OutOfRange {
+            parsed,
+            range: *range,
+        });
+    }
+    Ok(parsed)
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{parse_bandwidth_range, BandwidthRange};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+
+    impl Serialize for BandwidthRange {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for BandwidthRange {
+        fn deserialize<D>(d: D) -> Result<BandwidthRange, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct V;
+
+            impl de::Visitor<'_> for V {
+                type Value = BandwidthRange;
+
+                fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                    fmt.write_str("a bandwidth range")
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<BandwidthRange, E>
+                where
+                    E: de::Error,
+                {
+                    parse_bandwidth_range(v)
+                        .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+                }
+            }
+
+            d.deserialize_str(V)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_bandwidth;
+
+    #[test]
+    fn test_from_str() {
+        let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+        assert_eq!("100Mbps..1Gbps".parse::<BandwidthRange>(), Ok(range));
+        assert_eq!("100Mbps-1Gbps".parse::<BandwidthRange>(), Ok(range));
+        assert_eq!("100Mbps - 1Gbps".parse::<BandwidthRange>(), Ok(range));
+        assert_eq!(
+            "1Gbps-100Mbps".parse::<BandwidthRange>(),
+            Err(Error::InvalidRange)
+        );
+        assert_eq!("1Gbps".parse::<BandwidthRange>(), Err(Error::InvalidRange));
+        assert!("1Gbit-2Gbps".parse::<BandwidthRange>().is_err());
+
+        let human: HumanBandwidthRange = "100Mbps - 1Gbps".parse().unwrap();
+        assert_eq!(*human, range);
+        assert_eq!(BandwidthRange::from(human), range);
+        assert_eq!(human.to_string(), range.to_string());
+        assert_eq!(human.to_string().parse(), Ok(human));
+        assert_eq!(
+            HumanBandwidthRange::from(range).min(),
+            Bandwidth::from_mbps(100)
+        );
+    }
+
+    #[test]
+    fn test_step_by() {
+        let range = BandwidthRange::new(
+            parse_bandwidth("100Mbps").unwrap(),
+            parse_bandwidth("1Gbps").unwrap(),
+        );
+        let steps: Vec<_> = range.step_by(Bandwidth::from_mbps(100)).collect();
+        assert_eq!(steps.len(), 10);
+        assert_eq!(steps[0], Bandwidth::from_mbps(100));
+        assert_eq!(steps[4], Bandwidth::from_mbps(500));
+        assert_eq!(steps[9], Bandwidth::from_gbps(1));
+
+        let steps: Vec<_> = range.step_by(Bandwidth::from_mbps(400)).collect();
+        assert_eq!(
+            steps,
+            [
+                Bandwidth::from_mbps(100),
+                Bandwidth::from_mbps(500),
+                Bandwidth::from_mbps(900),
+            ]
+        );
+
+        let single = BandwidthRange::new(Bandwidth::from_bps(5), Bandwidth::from_bps(5));
+        assert_eq!(
+            single.step_by(Bandwidth::from_bps(1)).collect::<Vec<_>>(),
+            [Bandwidth::from_bps(5)]
+        );
+
+        // Stops instead of overflowing
+        let top = BandwidthRange::new(Bandwidth::new(u64::MAX, 0), Bandwidth::MAX);
+        assert_eq!(top.step_by(Bandwidth::from_mbps(600)).count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    fn test_step_by_zero() {
+        let range = BandwidthRange::new(Bandwidth::ZERO, Bandwidth::from_gbps(1));
+        let _ = range.step_by(Bandwidth::ZERO);
+    }
+
+    #[test]
+    fn test_geometric_step_by() {
+        let range = BandwidthRange::new(Bandwidth::from_kbps(1), Bandwidth::from_mbps(5));
+        let steps: Vec<_> = range.geometric_step_by(2.0).collect();
+        assert_eq!(steps.len(), 13);
+        assert_eq!(steps[0], Bandwidth::from_kbps(1));
+        assert_eq!(steps[1], Bandwidth::from_kbps(2));
+        assert_eq!(steps[12], Bandwidth::from_kbps(4096));
+
+        let zero = BandwidthRange::new(Bandwidth::ZERO, Bandwidth::from_gbps(1));
+        assert_eq!(
+            zero.geometric_step_by(2.0).collect::<Vec<_>>(),
+            [Bandwidth::ZERO]
+        );
+
+        let top = BandwidthRange::new(Bandwidth::new(u64::MAX / 2, 0), Bandwidth::MAX);
+        assert!(top.geometric_step_by(10.0).count() < 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "ratio must be greater than 1")]
+    fn test_geometric_step_by_invalid_ratio() {
+        let range = BandwidthRange::new(Bandwidth::ZERO, Bandwidth::from_gbps(1));
+        let _ = range.geometric_step_by(1.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let range = BandwidthRange::new(
+            parse_bandwidth("100Mbps").unwrap(),
+            parse_bandwidth("1Gbps").unwrap(),
+        );
+        assert_eq!(range.to_string(), "100Mbps..1Gbps");
+        assert_eq!(parse_bandwidth_range(&range.to_string()), Ok(range));
+
+        let range = BandwidthRange::new(Bandwidth::new(0, 150_024), Bandwidth::new(9420, 0));
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(range.to_string(), "150.024kbps..9.42Tbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(range.to_string(), "150kbps 24bps..9Tbps 420Gbps");
+        assert_eq!(parse_bandwidth_range(&range.to_string()), Ok(range));
+
+        let above = BandwidthRange::unbounded_above(Bandwidth::from_mbps(100));
+        let below = BandwidthRange::unbounded_below(Bandwidth::from_gbps(1));
+        let full = BandwidthRange::new(Bandwidth::ZERO, Bandwidth::MAX);
+        let point = BandwidthRange::new(Bandwidth::from_bps(5), Bandwidth::from_bps(5));
+        assert_eq!(above.to_string(), "100Mbps..");
+        assert_eq!(below.to_string(), "..1Gbps");
+        assert_eq!(full.to_string(), "..");
+        assert_eq!(point.to_string(), "5bps..5bps");
+        for range in [above, below, full, point] {
+            assert_eq!(parse_bandwidth_range(&range.to_string()), Ok(range));
+        }
+
+        // the width pads the whole range, the precision applies to each bound
+        let range = BandwidthRange::new(Bandwidth::from_mbps(1), Bandwidth::from_mbps(2));
+        assert_eq!(format!("{:>16}", range), "    1Mbps..2Mbps");
+        assert_eq!(format!("{:16}", range), "    1Mbps..2Mbps");
+        assert_eq!(format!("{:_<16}", range), "1Mbps..2Mbps____");
+        assert_eq!(format!("{:^14}", above), "  100Mbps..   ");
+        assert_eq!(format!("{:4}", range), "1Mbps..2Mbps");
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(format!("{:>18.1}", range), "  1.0Mbps..2.0Mbps");
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            parse_bandwidth_range("0bps-1bps"),
+            Ok(BandwidthRange::new(Bandwidth::ZERO, Bandwidth::from_bps(1)))
+        );
+        assert_eq!(
+            parse_bandwidth_range(" 1Gbps 500Mbps - 2.5Gbps "),
+            Ok(BandwidthRange::new(
+                Bandwidth::new(1, 500_000_000),
+                Bandwidth::new(2, 500_000_000)
+            ))
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Gbps-1Gbps"),
+            Ok(BandwidthRange::new(
+                Bandwidth::from_gbps(1),
+                Bandwidth::from_gbps(1)
+            ))
+        );
+        assert_eq!(parse_bandwidth_range("1Gbps"), Err(Error::InvalidRange));
+        assert_eq!(
+            Error::InvalidRange.to_string(),
+            "invalid bandwidth range, expected `min..max` (or `min-max`) \
+            with min not greater than max"
+        );
+        assert_eq!(
+            parse_bandwidth_range("2Gbps-1Gbps"),
+            Err(Error::InvalidRange)
+        );
+        assert_eq!(parse_bandwidth_range("-1Gbps"), Err(Error::Empty));
+        assert_eq!(parse_bandwidth_range("1Gbps-"), Err(Error::Empty));
+        assert_eq!(
+            parse_bandwidth_range("1Gbps-2Gbps-3Gbps"),
+            Err(Error::InvalidCharacter(11))
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Gbps-2byte/s"),
+            Err(Error::UnknownUnit {
+                start: 7,
+                end: 13,
+                unit: "byte/s".into(),
+                value: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_dots() {
+        assert_eq!(
+            parse_bandwidth_range("1.5Gbps..2.5Gbps"),
+            Ok(BandwidthRange::new(
+                Bandwidth::new(1, 500_000_000),
+                Bandwidth::new(2, 500_000_000)
+            ))
+        );
+        assert_eq!(
+            parse_bandwidth_range(" 1Gbps .. 2Gbps "),
+            Ok(BandwidthRange::new(
+                Bandwidth::from_gbps(1),
+                Bandwidth::from_gbps(2)
+            ))
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Gbps.."),
+            Ok(BandwidthRange::unbounded_above(Bandwidth::from_gbps(1)))
+        );
+        assert_eq!(
+            parse_bandwidth_range("..1Gbps"),
+            Ok(BandwidthRange::unbounded_below(Bandwidth::from_gbps(1)))
+        );
+        assert_eq!(
+            parse_bandwidth_range(" .. "),
+            Ok(BandwidthRange::new(Bandwidth::ZERO, Bandwidth::MAX))
+        );
+        assert_eq!(
+            parse_bandwidth_range("2Gbps..1Gbps"),
+            Err(Error::InvalidRange)
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Gbps..2byte/s"),
+            Err(Error::UnknownUnit {
+                start: 8,
+                end: 14,
+                unit: "byte/s".into(),
+                value: 2,
+            })
+        );
+        assert_eq!(
+            parse_bandwidth_range("1..2Gbps"),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 1,
+                unit: "".into(),
+                value: 1,
+            })
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Gbps...2Gbps"),
+            Err(Error::NumberExpected(7))
+        );
+    }
+
+    #[test]
+    fn test_clamp() {
+        let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+        assert_eq!(range.clamp(Bandwidth::ZERO), Bandwidth::from_mbps(100));
+        assert_eq!(
+            range.clamp(Bandwidth::from_mbps(100)),
+            Bandwidth::from_mbps(100)
+        );
+        assert_eq!(
+            range.clamp(Bandwidth::from_mbps(500)),
+            Bandwidth::from_mbps(500)
+        );
+        assert_eq!(
+            range.clamp(Bandwidth::from_gbps(1)),
+            Bandwidth::from_gbps(1)
+        );
+        assert_eq!(range.clamp(Bandwidth::MAX), Bandwidth::from_gbps(1));
+        let above = BandwidthRange::unbounded_above(Bandwidth::from_mbps(100));
+        assert_eq!(above.clamp(Bandwidth::MAX), Bandwidth::MAX);
+        assert!(above.contains(Bandwidth::MAX));
+        assert!(!above.contains(Bandwidth::new(0, 99_999_999)));
+        let below = BandwidthRange::unbounded_below(Bandwidth::from_gbps(1));
+        assert_eq!(
+            below.clamp(Bandwidth::from_gbps(2)),
+            Bandwidth::from_gbps(1)
+        );
+        assert!(below.contains(Bandwidth::ZERO));
+        assert!(!below.contains(Bandwidth::new(1, 1)));
+    }
+
+    #[test]
+    fn test_contains() {
+        let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+        assert!(range.contains(Bandwidth::from_mbps(100)));
+        assert!(range.contains(Bandwidth::from_mbps(500)));
+        assert!(range.contains(Bandwidth::from_gbps(1)));
+        assert!(!range.contains(Bandwidth::new(0, 99_999_999)));
+        assert!(!range.contains(Bandwidth::new(1, 1)));
+        let point = BandwidthRange::new(Bandwidth::ZERO, Bandwidth::ZERO);
+        assert!(point.contains(Bandwidth::ZERO));
+    }
+
+    #[test]
+    fn test_parse_checked() {
+        let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+        assert_eq!(
+            parse_bandwidth_checked("1Gbps", &range),
+            Ok(Bandwidth::from_gbps(1))
+        );
+        assert_eq!(
+            parse_bandwidth_checked("10Mbps", &range),
+            Err(BandwidthCheckError::OutOfRange {
+                parsed: Bandwidth::from_mbps(10),
+                range,
+            })
+        );
+        assert_eq!(
+            parse_bandwidth_checked("1Gbps 1bps", &range),
+            Err(BandwidthCheckError::OutOfRange {
+                parsed: Bandwidth::new(1, 1),
+                range,
+            })
+        );
+        assert_eq!(
+            parse_bandwidth_checked("1Gbit", &range),
+            Err(BandwidthCheckError::Parse(Error::UnknownUnit {
+                start: 1,
+                end: 5,
+                unit: "Gbit".into(),
+                value: 1,
+            }))
+        );
+        assert_eq!(
+            parse_bandwidth_checked("10Mbps", &range)
+                .unwrap_err()
+                .to_string(),
+            "bandwidth 10Mbps is out of range 100Mbps..1Gbps"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let range = BandwidthRange::new(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(json, r#""100Mbps..1Gbps""#);
+        assert_eq!(
+            serde_json::from_str::<BandwidthRange>(&json).unwrap(),
+            range
+        );
+        assert_eq!(
+            serde_json::from_str::<BandwidthRange>(r#""100Mbps-1Gbps""#).unwrap(),
+            range
+        );
+        let above = BandwidthRange::unbounded_above(Bandwidth::from_mbps(100));
+        let json = serde_json::to_string(&above).unwrap();
+        assert_eq!(json, r#""100Mbps..""#);
+        assert_eq!(
+            serde_json::from_str::<BandwidthRange>(&json).unwrap(),
+            above
+        );
+        assert!(serde_json::from_str::<BandwidthRange>(r#""1Gbps-1Mbps""#).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "range minimum is greater than its maximum")]
+    fn test_new_inverted() {
+        let _ = BandwidthRange::new(Bandwidth::from_gbps(1), Bandwidth::ZERO);
+    }
+}
@@ -0,0 +1,200 @@
+//! Parsing bandwidth from readers
+
+use crate::{parse_bandwidth_with_options, Error, ParseOptions};
+use bandwidth::Bandwidth;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+/// Error reading or parsing human-friendly bandwidth from a reader
+#[derive(Debug)]
+pub enum IoOrParseError {
+    /// Reading from the reader failed
+    ///
+    /// Input that exceeds the maximum length or is not valid UTF-8 is
+    /// reported as an error of kind [`io::ErrorKind::InvalidData`].
+    Io(io::Error),
+    /// The input was read but could not be parsed
+    Parse(Error),
+}
+
+impl StdError for IoOrParseError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            IoOrParseError::Io(e) => Some(e),
+            IoOrParseError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for IoOrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoOrParseError::Io(e) => write!(f, "failed to read bandwidth: {}", e),
+            IoOrParseError::Parse(e) => write!(f, "failed to parse bandwidth: {}", e),
+        }
+    }
+}
+
+impl From<io::Error> for IoOrParseError {
+    fn from(e: io::Error) -> IoOrParseError {
+        IoOrParseError::Io(e)
+    }
+}
+
+impl From<Error> for IoOrParseError {
+    fn from(e: Error) -> IoOrParseError {
+        IoOrParseError::Parse(e)
+    }
+}
+
+/// Reads a line from `r` and parses it with [`parse_bandwidth`][crate::parse_bandwidth]
+///
+/// See [`parse_bandwidth_from_reader_with_options`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_from_reader;
+/// use std::io::Cursor;
+///
+/// let mut input = Cursor::new("2Gbps 340Mbps\nnext line");
+/// assert_eq!(
+///     parse_bandwidth_from_reader(&mut input).unwrap(),
+///     Bandwidth::new(2, 340_000_000)
+/// );
+/// ```
+pub fn parse_bandwidth_from_reader(r: &mut impl io::Read) -> Result<Bandwidth, IoOrParseError> {
+    parse_bandwidth_from_reader_with_options(r, &ParseOptions::default())
+}
+
+/// Reads a line from `r` and parses it with
+/// [`parse_bandwidth_with_options`][crate::parse_bandwidth_with_options]
+///
+/// The input ends at the first newline or at the end of the reader. At most
+/// [`ParseOptions::max_input_len`] bytes are read, longer input fails with
+/// [`IoOrParseError::Io`]. The newline is consumed, but nothing after it.
+///
+/// The reader is read one byte at a time so that the rest of the stream is
+/// left untouched. For unbuffered readers like `File` or `TcpStream`, you may
+/// want to wrap them in a [`io::BufReader`] first.
+///
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::{parse_bandwidth_from_reader_with_options, ParseOptions};
+/// use std::io::Cursor;
+///
+/// let opts = ParseOptions::new().max_input_len(4);
+/// let mut input = Cursor::new("100Mbps");
+/// assert!(parse_bandwidth_from_reader_with_options(&mut input, &opts).is_err());
+/// ```
+pub fn parse_bandwidth_from_reader_with_options(
+    r: &mut impl io::Read,
+    opts: &ParseOptions,
+) -> Result<Bandwidth, IoOrParseError> {
+    let mut buf = Vec::new();
+    let mut byte = [0u8];
+    loop {
+        match r.read(&mut byte) {
+            Ok(0) => break,
+            Ok(_) if byte[0] == b'\n' => break,
+            Ok(_) => {
+                if buf.len() >= opts.max_input_len {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "bandwidth input exceeds maximum length",
+                    )
+                    .into());
+                }
+                buf.push(byte[0]);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let s = std::str::from_utf8(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(parse_bandwidth_with_options(s, opts)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn test_from_reader() {
+        let mut input = Cursor::new("1Gbps");
+        assert_eq!(
+            parse_bandwidth_from_reader(&mut input).unwrap(),
+            Bandwidth::new(1, 0)
+        );
+
+        let mut input = Cursor::new("10 Gbps 5Mbps\r\n32Mbps\n");
+        assert_eq!(
+            parse_bandwidth_from_reader(&mut input).unwrap(),
+            Bandwidth::new(10, 5_000_000)
+        );
+        assert_eq!(
+            parse_bandwidth_from_reader(&mut input).unwrap(),
+            Bandwidth::new(0, 32_000_000)
+        );
+        assert!(matches!(
+            parse_bandwidth_from_reader(&mut input),
+            Err(IoOrParseError::Parse(Error::Empty))
+        ));
+    }
+
+    #[test]
+    fn test_from_reader_leaves_rest() {
+        let mut input = Cursor::new("150.024kbps\nrest");
+        assert_eq!(
+            parse_bandwidth_from_reader(&mut input).unwrap(),
+            Bandwidth::new(0, 150_024)
+        );
+        let mut rest = String::new();
+        input.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "rest");
+    }
+
+    #[test]
+    fn test_from_reader_errors() {
+        let mut input = Cursor::new("10 byte/s");
+        assert!(matches!(
+            parse_bandwidth_from_reader(&mut input),
+            Err(IoOrParseError::Parse(Error::UnknownUnit { .. }))
+        ));
+
+        let mut input = Cursor::new(b"1\xffGbps".to_vec());
+        match parse_bandwidth_from_reader(&mut input) {
+            Err(IoOrParseError::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::InvalidData),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut input = Cursor::new("1".repeat(65) + "bps");
+        match parse_bandwidth_from_reader(&mut input) {
+            Err(IoOrParseError::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::InvalidData),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_reader_max_input_len() {
+        let opts = ParseOptions::new().max_input_len(7);
+        let mut input = Cursor::new("100Mbps\n");
+        assert_eq!(
+            parse_bandwidth_from_reader_with_options(&mut input, &opts).unwrap(),
+            Bandwidth::new(0, 100_000_000)
+        );
+        let mut input = Cursor::new("1000Mbps\n");
+        assert!(matches!(
+            parse_bandwidth_from_reader_with_options(&mut input, &opts),
+            Err(IoOrParseError::Io(_))
+        ));
+
+        let opts = ParseOptions::new().max_input_len(256);
+        let mut input = Cursor::new("1".to_string() + &" ".repeat(200) + "bps");
+        assert!(parse_bandwidth_from_reader_with_options(&mut input, &opts).is_ok());
+    }
+}
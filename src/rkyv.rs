@@ -0,0 +1,180 @@
+//! Zero-copy serialization of `HumanBandwidth` with rkyv
+
+use crate::HumanBandwidth;
+use bandwidth::Bandwidth;
+use core::fmt;
+use rkyv::bytecheck::Verify;
+use rkyv::munge::munge;
+use rkyv::primitive::{ArchivedU32, ArchivedU64};
+use rkyv::rancor::{fail, Fallible, Source};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+use std::error::Error as StdError;
+
+/// An archived [`HumanBandwidth`]
+///
+/// The bandwidth is stored as whole gigabits per second and the remaining
+/// bits per second, so it can be read in place without deserializing.
+///
+/// Validating an archive rejects a sub-gigabit part of 1Gbps or more, so a
+/// checked archive always holds a valid bandwidth.
+///
+/// Only available with the `rkyv` feature.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Portable, rkyv::bytecheck::CheckBytes,
+)]
+#[bytecheck(crate = rkyv::bytecheck, verify)]
+#[repr(C)]
+pub struct ArchivedHumanBandwidth {
+    gbps: ArchivedU64,
+    sub_bps: ArchivedU32,
+}
+
+impl ArchivedHumanBandwidth {
+    /// Returns the number of whole gigabits per second
+    pub fn as_gbps(&self) -> u64 {
+        self.gbps.to_native()
+    }
+
+    /// Returns the fractional part in bits per second
+    pub fn subgbps_bps(&self) -> u32 {
+        self.sub_bps.to_native()
+    }
+}
+
+/// The sub-gigabit part of an archive is 1Gbps or more
+#[derive(Debug)]
+struct SubGbpsOverflow(u32);
+
+impl fmt::Display for SubGbpsOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "sub-gigabit part of an archived bandwidth is {}bps, expected less than 1Gbps",
+            self.0
+        )
+    }
+}
+
+impl StdError for SubGbpsOverflow {}
+
+// SAFETY: `verify` only succeeds if the sub-gigabit part is below 1Gbps,
+// the one invariant of the archived type beyond its fields
+unsafe impl<C: Fallible + ?Sized> Verify<C> for ArchivedHumanBandwidth
+where
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        let sub_bps = self.sub_bps.to_native();
+        if sub_bps >= 1_000_000_000 {
+            fail!(SubGbpsOverflow(sub_bps));
+        }
+        Ok(())
+    }
+}
+
+/// Reads the bandwidth out of an archived [`HumanBandwidth`]
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{from_archived, ArchivedHumanBandwidth, HumanBandwidth};
+///
+/// let bw = HumanBandwidth::from(Bandwidth::new(1, 500));
+/// let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&bw).unwrap();
+/// let archived = rkyv::access::<ArchivedHumanBandwidth, rkyv::rancor::Error>(&bytes).unwrap();
+/// assert_eq!(from_archived(archived), Bandwidth::new(1, 500));
+/// ```
+pub fn from_archived(archived: &ArchivedHumanBandwidth) -> Bandwidth {
+    Bandwidth::new(archived.as_gbps(), archived.subgbps_bps())
+}
+
+impl Archive for HumanBandwidth {
+    type Archived = ArchivedHumanBandwidth;
+    type Resolver = ();
+
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedHumanBandwidth { gbps, sub_bps } = out);
+        self.as_gbps().resolve((), gbps);
+        self.subgbps_bps().resolve((), sub_bps);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for HumanBandwidth {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<HumanBandwidth, D> for ArchivedHumanBandwidth {
+    fn deserialize(&self, _: &mut D) -> Result<HumanBandwidth, D::Error> {
+        Ok(HumanBandwidth::from(from_archived(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn test_roundtrip() {
+        for bw in [
+            Bandwidth::ZERO,
+            Bandwidth::new(1, 500_000_000),
+            Bandwidth::new(9420, 1),
+            Bandwidth::MAX,
+        ] {
+            let bytes = rkyv::to_bytes::<Error>(&HumanBandwidth::from(bw)).unwrap();
+            assert_eq!(bytes.len(), 16);
+            let archived = rkyv::access::<ArchivedHumanBandwidth, Error>(&bytes).unwrap();
+            assert_eq!(archived.as_gbps(), bw.as_gbps());
+            assert_eq!(archived.subgbps_bps(), bw.subgbps_bps());
+            assert_eq!(from_archived(archived), bw);
+            let de = rkyv::deserialize::<HumanBandwidth, Error>(archived).unwrap();
+            assert_eq!(*de, bw);
+        }
+    }
+
+    #[test]
+    fn test_zero_copy() {
+        let bytes = rkyv::to_bytes::<Error>(&HumanBandwidth::from(Bandwidth::new(1, 5))).unwrap();
+        let archived = rkyv::access::<ArchivedHumanBandwidth, Error>(&bytes).unwrap();
+        // the archived value is a view into the buffer, not a copy
+        let start = bytes.as_ptr() as usize;
+        let addr = archived as *const ArchivedHumanBandwidth as usize;
+        assert!(addr >= start && addr < start + bytes.len());
+        assert_eq!(from_archived(archived), Bandwidth::new(1, 5));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let mut bytes = rkyv::to_bytes::<Error>(&HumanBandwidth::from(Bandwidth::MAX))
+            .unwrap()
+            .to_vec();
+        assert!(rkyv::access::<ArchivedHumanBandwidth, Error>(&bytes).is_ok());
+        // gbps is u64::MAX, set the sub-gigabit part to 1Gbps
+        bytes[8..12].copy_from_slice(&1_000_000_000u32.to_le_bytes());
+        let err = rkyv::access::<ArchivedHumanBandwidth, Error>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("1000000000bps"), "{}", err);
+        assert!(rkyv::from_bytes::<HumanBandwidth, Error>(&bytes).is_err());
+
+        bytes[8..12].copy_from_slice(&999_999_999u32.to_le_bytes());
+        assert_eq!(
+            *rkyv::from_bytes::<HumanBandwidth, Error>(&bytes).unwrap(),
+            Bandwidth::MAX
+        );
+    }
+
+    #[test]
+    fn test_vec() {
+        let vals: Vec<HumanBandwidth> = vec![
+            Bandwidth::from_mbps(100).into(),
+            Bandwidth::from_gbps(10).into(),
+        ];
+        let bytes = rkyv::to_bytes::<Error>(&vals).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<HumanBandwidth>>, Error>(&bytes).unwrap();
+        let bws: Vec<Bandwidth> = archived.iter().map(from_archived).collect();
+        assert_eq!(bws, [Bandwidth::from_mbps(100), Bandwidth::from_gbps(10)]);
+    }
+}
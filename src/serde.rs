@@ -24,9 +24,38 @@
 //!     bandwidth: Vec<Serde<Bandwidth>>,
 //! }
 //! ```
+//!
+//! # Accepted representations
+//!
+//! Serialization always emits the human-readable string, e.g. `"1.5Gbps"`.
+//!
+//! For human-readable formats (e.g. `serde_json`, `toml`), deserialization
+//! goes through `deserialize_any`, so the following are accepted:
+//!
+//! * a string (`visit_str`), e.g. `"1.5Gbps"`
+//! * a non-negative integer (`visit_u64`, `visit_i64`), interpreted as raw
+//!   bps, e.g. `1500000000`, or `bandwidth = 1500000000` in TOML
+//! * a non-negative float (`visit_f64`), interpreted as raw bps after
+//!   dropping the fraction, e.g. `1.5e9`
+//! * a sequence of two integers (`visit_seq`), interpreted as `[gbps, sub_bps]`,
+//!   e.g. `[1, 500000000]`
+//!
+//! Non-self-describing formats (e.g. `bincode`) do not support
+//! `deserialize_any`, so compact formats go through `deserialize_str`.
+//! Depending on the format, this calls `visit_str` or `visit_bytes`, where the
+//! bytes are interpreted as a UTF-8 string. This is also the case for CBOR
+//! with `ciborium`, which only accepts the string form.
 
+use crate::{BandwidthExt, ParseOptions};
 use bandwidth::Bandwidth;
 use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde_json")]
+mod json;
+pub mod map;
+#[cfg(feature = "schemars")]
+mod schema;
+pub mod unlimited;
 use std::fmt;
 use std::ops::{Deref, DerefMut};
 
@@ -95,30 +124,103 @@ impl<T> From<T> for Serde<T> {
     }
 }
 
-impl<'de> Deserialize<'de> for Serde<Bandwidth> {
-    fn deserialize<D>(d: D) -> Result<Serde<Bandwidth>, D::Error>
+struct V(ParseOptions);
+
+impl<'de> de::Visitor<'de> for V {
+    type Value = Bandwidth;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a bandwidth")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
     where
-        D: Deserializer<'de>,
+        E: de::Error,
     {
-        struct V;
+        crate::parse_bandwidth_with_options(v, &self.0)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
 
-        impl de::Visitor<'_> for V {
-            type Value = Bandwidth;
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        match std::str::from_utf8(v) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
 
-            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-                fmt.write_str("a bandwidth")
-            }
+    fn visit_u64<E>(self, v: u64) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        Ok(Bandwidth::from_bps(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        u64::try_from(v)
+            .map(Bandwidth::from_bps)
+            .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+    }
 
-            fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
-            where
-                E: de::Error,
-            {
-                crate::parse_bandwidth(v)
-                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    fn visit_f64<E>(self, v: f64) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        // the cast saturates, so out of range values do not fit a bandwidth
+        if v.is_finite() && v >= 0.0 {
+            if let Some(bw) = Bandwidth::from_bps_u128(v as u128) {
+                return Ok(bw);
             }
         }
+        Err(E::invalid_value(de::Unexpected::Float(v), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Bandwidth, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let gbps: u64 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let bps: u64 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(3, &self));
+        }
+        if bps >= 1_000_000_000 {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(bps),
+                &"sub-gbps part less than 1000000000",
+            ));
+        }
+        Ok(Bandwidth::new(gbps, bps as u32))
+    }
+}
+
+/// Deserializes a `Bandwidth`, parsing strings with `opts`
+fn deserialize_with_options<'de, D>(d: D, opts: ParseOptions) -> Result<Bandwidth, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if d.is_human_readable() {
+        d.deserialize_any(V(opts))
+    } else {
+        d.deserialize_str(V(opts))
+    }
+}
 
-        d.deserialize_str(V).map(Serde)
+impl<'de> Deserialize<'de> for Serde<Bandwidth> {
+    fn deserialize<D>(d: D) -> Result<Serde<Bandwidth>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_with_options(d, ParseOptions::default()).map(Serde)
     }
 }
 
@@ -134,6 +236,16 @@ impl<'de> Deserialize<'de> for Serde<Option<Bandwidth>> {
     }
 }
 
+impl<'de> Deserialize<'de> for Serde<Vec<Bandwidth>> {
+    fn deserialize<D>(d: D) -> Result<Serde<Vec<Bandwidth>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = Vec::<Serde<Bandwidth>>::deserialize(d)?;
+        Ok(Serde(v.into_iter().map(Serde::into_inner).collect()))
+    }
+}
+
 impl ser::Serialize for Serde<&Bandwidth> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -177,6 +289,24 @@ impl ser::Serialize for Serde<Option<Bandwidth>> {
     }
 }
 
+impl ser::Serialize for Serde<&Vec<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_seq(self.0.iter().map(Serde))
+    }
+}
+
+impl ser::Serialize for Serde<Vec<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Serde(&self.0).serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +350,145 @@ mod tests {
         let foo = serde_json::from_str::<Foo>(json).unwrap();
         assert_eq!(foo.bandwidth, None);
     }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Bar {
+        #[serde(with = "super")]
+        bandwidth: Bandwidth,
+    }
+
+    #[test]
+    fn with_vec() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Vec<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": ["1Gbps", "500Mbps", "100kbps"]}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(
+            foo.bandwidth,
+            [
+                Bandwidth::from_gbps(1),
+                Bandwidth::from_mbps(500),
+                Bandwidth::from_kbps(100)
+            ]
+        );
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":["1Gbps","500Mbps","100kbps"]}"#);
+
+        let json = r#"{"bandwidth": []}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert!(foo.bandwidth.is_empty());
+        assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"bandwidth":[]}"#);
+
+        let json = r#"{"bandwidth": ["1Gbps", "1Gbit"]}"#;
+        assert!(serde_json::from_str::<Foo>(json).is_err());
+        let json = r#"{"bandwidth": "1Gbps"}"#;
+        assert!(serde_json::from_str::<Foo>(json).is_err());
+    }
+
+    #[test]
+    fn json_representations() {
+        let json = r#"{"bandwidth": 1500000000}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.bandwidth, Bandwidth::new(1, 500_000_000));
+        let reverse = serde_json::to_string(&bar).unwrap();
+        assert_eq!(serde_json::from_str::<Bar>(&reverse).unwrap(), bar);
+
+        let json = r#"{"bandwidth": 1000000}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.bandwidth, Bandwidth::from_mbps(1));
+
+        let json = r#"{"bandwidth": 1.5e9}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.bandwidth, Bandwidth::new(1, 500_000_000));
+        let json = r#"{"bandwidth": 1000.9}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.bandwidth, Bandwidth::from_kbps(1));
+        let json = r#"{"bandwidth": 0.5}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.bandwidth, Bandwidth::ZERO);
+        let json = r#"{"bandwidth": -1.5}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+        let json = r#"{"bandwidth": 1e30}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+
+        let json = r#"{"bandwidth": [2, 5]}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.bandwidth, Bandwidth::new(2, 5));
+
+        let json = r#"{"bandwidth": [2]}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+        let json = r#"{"bandwidth": [2, 5, 7]}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+        let json = r#"{"bandwidth": [2, 1000000000]}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+        let json = r#"{"bandwidth": -1}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+        let json = r#"{"bandwidth": "1 byte/s"}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+    }
+
+    #[test]
+    fn integers_and_floats() {
+        use serde::de::value::{Error, F64Deserializer, I64Deserializer};
+
+        // TOML integers are signed
+        let de = I64Deserializer::<Error>::new(1_000_000);
+        assert_eq!(deserialize::<Bandwidth, _>(de), Ok(Bandwidth::from_mbps(1)));
+        let de = I64Deserializer::<Error>::new(-1);
+        assert!(deserialize::<Bandwidth, _>(de).is_err());
+
+        let de = F64Deserializer::<Error>::new(1.5e9);
+        assert_eq!(
+            deserialize::<Bandwidth, _>(de),
+            Ok(Bandwidth::new(1, 500_000_000))
+        );
+        let de = F64Deserializer::<Error>::new((1u128 << 70) as f64);
+        assert_eq!(
+            deserialize::<Bandwidth, _>(de),
+            Ok(Bandwidth::new(1_180_591_620_717, 411_303_424))
+        );
+        for v in [f64::NAN, f64::INFINITY, -0.5, 1.9e28] {
+            let de = F64Deserializer::<Error>::new(v);
+            assert!(deserialize::<Bandwidth, _>(de).is_err(), "{}", v);
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        use serde::de::value::{BytesDeserializer, Error};
+
+        let de = BytesDeserializer::<Error>::new(b"10Mbps");
+        assert_eq!(
+            deserialize::<Bandwidth, _>(de),
+            Ok(Bandwidth::from_mbps(10))
+        );
+        let de = BytesDeserializer::<Error>::new(b"\xff");
+        assert!(deserialize::<Bandwidth, _>(de).is_err());
+    }
+
+    #[test]
+    fn bincode_roundtrip() {
+        let bar = Bar {
+            bandwidth: Bandwidth::new(4, 500_000_000),
+        };
+        let bytes = bincode::serialize(&bar).unwrap();
+        assert_eq!(bincode::deserialize::<Bar>(&bytes).unwrap(), bar);
+    }
+
+    #[test]
+    fn cbor_roundtrip() {
+        let bar = Bar {
+            bandwidth: Bandwidth::new(0, 150_024),
+        };
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(&bar, &mut bytes).unwrap();
+        assert_eq!(
+            ciborium::de::from_reader::<Bar, _>(&bytes[..]).unwrap(),
+            bar
+        );
+    }
 }
@@ -0,0 +1,78 @@
+//! Conversions between bandwidths and `serde_json::Value`
+
+use super::deserialize_with_options;
+use crate::{FormattedBandwidth, HumanBandwidth, ParseOptions};
+use serde_json::Value;
+use std::convert::TryFrom;
+
+/// Converts to the human-readable string, e.g. `"1.5Gbps"`
+///
+/// Only available with the `serde_json` feature.
+impl From<FormattedBandwidth> for Value {
+    fn from(val: FormattedBandwidth) -> Value {
+        Value::String(val.to_string())
+    }
+}
+
+/// Reads a bandwidth from a JSON value
+///
+/// The value is deserialized like a field using
+/// [`human_bandwidth::serde`][crate::serde]: a string is parsed, and a number
+/// is taken as raw bps.
+///
+/// Only available with the `serde_json` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::HumanBandwidth;
+/// use std::convert::TryFrom;
+///
+/// let val = serde_json::json!("1.5Gbps");
+/// assert_eq!(*HumanBandwidth::try_from(&val).unwrap(), Bandwidth::new(1, 500_000_000));
+/// ```
+impl<'a> TryFrom<&'a Value> for HumanBandwidth {
+    type Error = serde_json::Error;
+
+    fn try_from(val: &'a Value) -> Result<HumanBandwidth, serde_json::Error> {
+        deserialize_with_options(val, ParseOptions::default()).map(HumanBandwidth::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format_bandwidth;
+    use bandwidth::Bandwidth;
+    use serde_json::json;
+
+    #[test]
+    fn test_into_value() {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "bandwidth".to_string(),
+            format_bandwidth(Bandwidth::from_mbps(32)).into(),
+        );
+        assert_eq!(Value::Object(map), json!({"bandwidth": "32Mbps"}));
+    }
+
+    #[test]
+    fn test_try_from_value() {
+        let bw = |val: Value| HumanBandwidth::try_from(&val).map(Bandwidth::from);
+        assert_eq!(
+            bw(json!("1Gbps 500Mbps")).unwrap(),
+            Bandwidth::new(1, 500_000_000)
+        );
+        assert_eq!(bw(json!(1000)).unwrap(), Bandwidth::from_kbps(1));
+        assert_eq!(bw(json!([1, 5])).unwrap(), Bandwidth::new(1, 5));
+        assert!(bw(json!("1Gbit")).is_err());
+        assert!(bw(json!(-1)).is_err());
+        assert_eq!(bw(json!(1.5)).unwrap(), Bandwidth::from_bps(1));
+        assert!(bw(json!(-1.5)).is_err());
+        assert!(bw(json!(null)).is_err());
+
+        let val: Value = format_bandwidth(Bandwidth::new(9420, 0)).into();
+        assert_eq!(bw(val).unwrap(), Bandwidth::new(9420, 0));
+    }
+}
@@ -0,0 +1,126 @@
+//! Serde support for maps of humanized bandwidth.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde::map")]
+//!     limits: HashMap<String, Bandwidth>,
+//! }
+//! ```
+
+use super::Serde;
+use bandwidth::Bandwidth;
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+/// Serializes a `HashMap<K, Bandwidth>` with values in human-readable format.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<K, H, S>(map: &HashMap<K, Bandwidth, H>, s: S) -> Result<S::Ok, S::Error>
+where
+    K: Serialize,
+    S: Serializer,
+{
+    let mut m = s.serialize_map(Some(map.len()))?;
+    for (k, v) in map {
+        m.serialize_entry(k, &Serde(v))?;
+    }
+    m.end()
+}
+
+/// Deserializes a `HashMap<K, Bandwidth>` with values in human-readable format.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'de, K, H, D>(d: D) -> Result<HashMap<K, Bandwidth, H>, D::Error>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    H: BuildHasher + Default,
+    D: Deserializer<'de>,
+{
+    struct V<K, H>(PhantomData<(K, H)>);
+
+    impl<'de, K, H> de::Visitor<'de> for V<K, H>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        H: BuildHasher + Default,
+    {
+        type Value = HashMap<K, Bandwidth, H>;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a map of bandwidths")
+        }
+
+        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut map =
+                HashMap::with_capacity_and_hasher(access.size_hint().unwrap_or(0), H::default());
+            while let Some((k, Serde(v))) = access.next_entry::<K, Serde<Bandwidth>>()? {
+                map.insert(k, v);
+            }
+            Ok(map)
+        }
+    }
+
+    d.deserialize_map(V(PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Foo {
+        #[serde(with = "super")]
+        limits: HashMap<String, Bandwidth>,
+    }
+
+    #[test]
+    fn with() {
+        let json = r#"{"limits": {"eth0": "1Gbps", "eth1": "10Gbps"}}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.limits.len(), 2);
+        assert_eq!(foo.limits["eth0"], Bandwidth::from_gbps(1));
+        assert_eq!(foo.limits["eth1"], Bandwidth::from_gbps(10));
+
+        let reverse = serde_json::to_value(&foo).unwrap();
+        assert_eq!(
+            reverse,
+            serde_json::json!({"limits": {"eth0": "1Gbps", "eth1": "10Gbps"}})
+        );
+
+        let json = r#"{"limits": {}}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert!(foo.limits.is_empty());
+        assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"limits":{}}"#);
+
+        let json = r#"{"limits": {"eth0": "1Gbit"}}"#;
+        assert!(serde_json::from_str::<Foo>(json).is_err());
+    }
+
+    #[test]
+    fn with_integer_keys() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Bar {
+            #[serde(with = "super")]
+            queues: HashMap<u8, Bandwidth>,
+        }
+
+        let json = r#"{"queues": {"0": "100Mbps"}}"#;
+        let bar = serde_json::from_str::<Bar>(json).unwrap();
+        assert_eq!(bar.queues[&0], Bandwidth::from_mbps(100));
+        let reverse = serde_json::to_string(&bar).unwrap();
+        assert_eq!(reverse, r#"{"queues":{"0":"100Mbps"}}"#);
+    }
+}
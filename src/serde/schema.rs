@@ -0,0 +1,147 @@
+//! JSON schemas for the `Serde` wrapper
+
+use super::Serde;
+use bandwidth::Bandwidth;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+/// A string schema with the supported units and an example, e.g. `"100Mbps"`
+///
+/// Fields using `#[serde(with = "human_bandwidth::serde")]` get this schema
+/// with `#[schemars(with = "human_bandwidth::serde::Serde<Bandwidth>")]`.
+///
+/// Only available with the `schemars` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::serde::Serde;
+/// use schemars::JsonSchema;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, JsonSchema)]
+/// struct Foo {
+///     #[serde(with = "human_bandwidth::serde")]
+///     #[schemars(with = "Serde<Bandwidth>")]
+///     bandwidth: Bandwidth,
+/// }
+///
+/// let schema = schemars::schema_for!(Foo);
+/// assert_eq!(schema.as_value()["$defs"]["Bandwidth"]["type"], "string");
+/// ```
+impl JsonSchema for Serde<Bandwidth> {
+    fn schema_name() -> Cow<'static, str> {
+        "Bandwidth".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "human_bandwidth::Bandwidth".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A bandwidth in human-readable form, like `1.5Gbps` or \
+                `9Tbps 420Gbps`. Supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, \
+                Ebps, Bps, KBps, MBps, GBps, TBps",
+            "examples": ["100Mbps"],
+        })
+    }
+}
+
+/// The schema of `Serde<Bandwidth>`, allowing `null`
+///
+/// Only available with the `schemars` feature.
+impl JsonSchema for Serde<Option<Bandwidth>> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <Option<Serde<Bandwidth>>>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        <Option<Serde<Bandwidth>>>::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Option<Serde<Bandwidth>>>::json_schema(generator)
+    }
+}
+
+/// An array of `Serde<Bandwidth>` schemas
+///
+/// Only available with the `schemars` feature.
+impl JsonSchema for Serde<Vec<Bandwidth>> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <Vec<Serde<Bandwidth>>>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        <Vec<Serde<Bandwidth>>>::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Vec<Serde<Bandwidth>>>::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schemars::schema_for;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[test]
+    fn test_schema() {
+        let schema = schema_for!(Serde<Bandwidth>);
+        let schema = schema.as_value();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["title"], "Bandwidth");
+        assert_eq!(schema["examples"], json!(["100Mbps"]));
+        let description = schema["description"].as_str().unwrap();
+        assert!(description.contains("Mbps, Gbps"));
+        assert!(description.contains("MBps"));
+
+        let schema = schema_for!(Serde<Option<Bandwidth>>);
+        assert_eq!(
+            schema.as_value()["anyOf"],
+            json!([{"$ref": "#/$defs/Bandwidth"}, {"type": "null"}])
+        );
+        let schema = schema_for!(Serde<Vec<Bandwidth>>);
+        assert_eq!(schema.as_value()["type"], "array");
+    }
+
+    #[test]
+    fn test_struct_schema() {
+        #[derive(Serialize, Deserialize, JsonSchema)]
+        #[allow(dead_code)]
+        struct Foo {
+            #[serde(with = "crate::serde")]
+            #[schemars(with = "Serde<Bandwidth>")]
+            limit: Bandwidth,
+            #[serde(with = "crate::serde")]
+            #[schemars(with = "Serde<Option<Bandwidth>>")]
+            burst: Option<Bandwidth>,
+        }
+
+        let schema = schema_for!(Foo);
+        let schema = schema.as_value();
+        assert_eq!(
+            schema["properties"]["limit"],
+            json!({"$ref": "#/$defs/Bandwidth"})
+        );
+        assert_eq!(
+            schema["properties"]["burst"],
+            json!({"anyOf": [{"$ref": "#/$defs/Bandwidth"}, {"type": "null"}]})
+        );
+        assert_eq!(schema["$defs"]["Bandwidth"]["type"], "string");
+    }
+}
@@ -0,0 +1,101 @@
+//! Serde support for humanized bandwidth that may be unlimited.
+//!
+//! [`Bandwidth::MAX`] serializes as `"unlimited"`, and deserialization
+//! accepts the keywords of [`ParseOptions::allow_unlimited`] in addition to
+//! the representations of [`human_bandwidth::serde`][crate::serde].
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde::unlimited")]
+//!     limit: Bandwidth,
+//! }
+//! ```
+
+use super::{deserialize_with_options, Serde};
+use crate::ParseOptions;
+use bandwidth::Bandwidth;
+use serde::{Deserializer, Serialize, Serializer};
+
+/// Serializes a `Bandwidth` in human-readable format, or `"unlimited"` for
+/// [`Bandwidth::MAX`].
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<S>(bw: &Bandwidth, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if *bw == Bandwidth::MAX {
+        s.serialize_str("unlimited")
+    } else {
+        Serde(bw).serialize(s)
+    }
+}
+
+/// Deserializes a `Bandwidth` in human-readable format, mapping `"unlimited"`
+/// and friends to [`Bandwidth::MAX`].
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'de, D>(d: D) -> Result<Bandwidth, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_with_options(d, ParseOptions::new().allow_unlimited(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Foo {
+        #[serde(with = "super")]
+        limit: Bandwidth,
+    }
+
+    #[test]
+    fn with() {
+        let json = r#"{"limit": "unlimited"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.limit, Bandwidth::MAX);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"limit":"unlimited"}"#);
+
+        let json = r#"{"limit": "∞"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.limit, Bandwidth::MAX);
+
+        let json = r#"{"limit": "1Gbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.limit, Bandwidth::from_gbps(1));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"limit":"1Gbps"}"#);
+
+        let json = r#"{"limit": 1000}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.limit, Bandwidth::from_kbps(1));
+
+        let json = r#"{"limit": "boundless"}"#;
+        assert!(serde_json::from_str::<Foo>(json).is_err());
+    }
+
+    #[test]
+    fn plain_serde_rejects_unlimited() {
+        #[derive(Debug, Deserialize)]
+        struct Bar {
+            #[serde(with = "crate::serde")]
+            #[allow(dead_code)]
+            limit: Bandwidth,
+        }
+
+        let json = r#"{"limit": "unlimited"}"#;
+        assert!(serde_json::from_str::<Bar>(json).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! Serde support for bandwidth as a raw integer of bits per second.
+//!
+//! This is useful for interoperability with systems that only understand
+//! integers. Values larger than `u64::MAX` bps cannot be represented and
+//! fail to serialize.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde_bps")]
+//!     bandwidth: Bandwidth,
+//! }
+//! ```
+//!
+//! Or use the `Serde` wrapper type:
+//!
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use human_bandwidth::serde_bps::Serde;
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     bandwidth: Vec<Serde<Bandwidth>>,
+//! }
+//! ```
+
+use bandwidth::Bandwidth;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// Deserializes a `Bandwidth` from an integer of bits per second.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Serde<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Serde::deserialize(d).map(Serde::into_inner)
+}
+
+/// Serializes a `Bandwidth` as an integer of bits per second.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Serde<&'a T>: Serialize,
+    S: Serializer,
+{
+    Serde::from(d).serialize(s)
+}
+
+/// A wrapper type which implements `Serialize` and `Deserialize` for
+/// types involving `Bandwidth`, using the raw bps representation.
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Serde<T>(T);
+
+impl<T> fmt::Debug for Serde<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T> Deref for Serde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Serde<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Serde<T> {
+    /// Consumes the `De`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Serde<T> {
+    fn from(val: T) -> Serde<T> {
+        Serde(val)
+    }
+}
+
+impl<'de> Deserialize<'de> for Serde<Bandwidth> {
+    fn deserialize<D>(d: D) -> Result<Serde<Bandwidth>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+
+        impl de::Visitor<'_> for V {
+            type Value = Bandwidth;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("a bandwidth in bits per second")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                Ok(Bandwidth::from_bps(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                u64::try_from(v)
+                    .map(Bandwidth::from_bps)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+        }
+
+        d.deserialize_u64(V).map(Serde)
+    }
+}
+
+impl<'de> Deserialize<'de> for Serde<Option<Bandwidth>> {
+    fn deserialize<D>(d: D) -> Result<Serde<Option<Bandwidth>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<Serde<Bandwidth>>::deserialize(d)? {
+            Some(Serde(dur)) => Ok(Serde(Some(dur))),
+            None => Ok(Serde(None)),
+        }
+    }
+}
+
+impl ser::Serialize for Serde<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let bps = u64::try_from(self.0.as_bps())
+            .map_err(|_| ser::Error::custom("bandwidth exceeds u64::MAX bps"))?;
+        bps.serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Serde<Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Serde(&self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Serde<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.0 {
+            Some(dur) => serializer.serialize_some(&Serde(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl ser::Serialize for Serde<Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Serde(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bw: Bandwidth,
+        }
+
+        let json = r#"{"bw": 1000000000}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bw, Bandwidth::from_gbps(1));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bw":1000000000}"#);
+
+        let json = r#"{"bw": 1500000123}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bw, Bandwidth::new(1, 500_000_123));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bw":1500000123}"#);
+
+        let json = r#"{"bw": "1Gbps"}"#;
+        assert!(serde_json::from_str::<Foo>(json).is_err());
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super", default)]
+            bw: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bw": 15000000}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bw, Some(Bandwidth::from_mbps(15)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bw":15000000}"#);
+
+        let json = r#"{"bw": null}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bw, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bw":null}"#);
+
+        let json = r#"{}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bw, None);
+    }
+
+    #[test]
+    fn overflow() {
+        let max = Bandwidth::from_bps(u64::MAX);
+        assert_eq!(
+            serde_json::to_string(&Serde(max)).unwrap(),
+            u64::MAX.to_string()
+        );
+        let over = max + Bandwidth::from_bps(1);
+        assert!(serde_json::to_string(&Serde(over)).is_err());
+        assert!(serde_json::to_string(&Serde(Bandwidth::MAX)).is_err());
+    }
+
+    #[test]
+    fn signed() {
+        use serde::de::value::{Error, I64Deserializer};
+
+        // TOML integers are signed
+        let de = I64Deserializer::<Error>::new(1_000_000);
+        assert_eq!(deserialize::<Bandwidth, _>(de), Ok(Bandwidth::from_mbps(1)));
+        let de = I64Deserializer::<Error>::new(i64::MAX);
+        assert_eq!(
+            deserialize::<Bandwidth, _>(de),
+            Ok(Bandwidth::from_bps(i64::MAX as u64))
+        );
+        let de = I64Deserializer::<Error>::new(-1);
+        let err = deserialize::<Bandwidth, _>(de).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: integer `-1`, expected a bandwidth in bits per second"
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Well-known network standard rates
+//!
+//! Network engineers routinely refer to link rates by the name of their
+//! standard, like `10GbE` or `OC-48`. This module maps those names to the
+//! corresponding [`Bandwidth`] and back.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::standards::{bandwidth_name, named_bandwidth};
+//!
+//! assert_eq!(named_bandwidth("10GbE"), Some(Bandwidth::new(10, 0)));
+//! assert_eq!(named_bandwidth("OC-3"), Some(Bandwidth::new(0, 155_520_000)));
+//! assert_eq!(bandwidth_name(Bandwidth::new(0, 1_544_000)), Some("T1"));
+//! ```
+//!
+//! [`StandardNetworkSpeed`] names the common link speeds in code instead.
+
+use bandwidth::Bandwidth;
+
+/// The table of known standards, as `(name, bandwidth)` pairs
+///
+/// * Ethernet: `10MbE`, `100MbE`, `1GbE`, `2.5GbE`, `5GbE`, `10GbE`, `25GbE`,
+///   `40GbE`, `50GbE`, `100GbE`, `200GbE`, `400GbE`, `800GbE`
+/// * T-carrier / digital signal: `DS0`, `T1`, `DS1`, `T3`, `DS3`
+/// * E-carrier: `E1`, `E3`
+/// * SONET optical carrier: `OC-1`, `OC-3`, `OC-12`, `OC-24`, `OC-48`,
+///   `OC-192`, `OC-768`
+///
+/// Several names may share the same rate (e.g. `T1` and `DS1`); the first
+/// one in the table is the canonical name returned by [`bandwidth_name`].
+/// To add a standard, append an entry to this table.
+pub const STANDARDS: &[(&str, Bandwidth)] = &[
+    ("10MbE", Bandwidth::new(0, 10_000_000)),
+    ("100MbE", Bandwidth::new(0, 100_000_000)),
+    ("1GbE", Bandwidth::new(1, 0)),
+    ("2.5GbE", Bandwidth::new(2, 500_000_000)),
+    ("5GbE", Bandwidth::new(5, 0)),
+    ("10GbE", Bandwidth::new(10, 0)),
+    ("25GbE", Bandwidth::new(25, 0)),
+    ("40GbE", Bandwidth::new(40, 0)),
+    ("50GbE", Bandwidth::new(50, 0)),
+    ("100GbE", Bandwidth::new(100, 0)),
+    ("200GbE", Bandwidth::new(200, 0)),
+    ("400GbE", Bandwidth::new(400, 0)),
+    ("800GbE", Bandwidth::new(800, 0)),
+    ("DS0", Bandwidth::new(0, 64_000)),
+    ("T1", Bandwidth::new(0, 1_544_000)),
+    ("DS1", Bandwidth::new(0, 1_544_000)),
+    ("T3", Bandwidth::new(0, 44_736_000)),
+    ("DS3", Bandwidth::new(0, 44_736_000)),
+    ("E1", Bandwidth::new(0, 2_048_000)),
+    ("E3", Bandwidth::new(0, 34_368_000)),
+    ("OC-1", Bandwidth::new(0, 51_840_000)),
+    ("OC-3", Bandwidth::new(0, 155_520_000)),
+    ("OC-12", Bandwidth::new(0, 622_080_000)),
+    ("OC-24", Bandwidth::new(1, 244_160_000)),
+    ("OC-48", Bandwidth::new(2, 488_320_000)),
+    ("OC-192", Bandwidth::new(9, 953_280_000)),
+    ("OC-768", Bandwidth::new(39, 813_120_000)),
+];
+
+/// Looks up the bandwidth of a well-known network standard
+///
+/// The name is matched case-insensitively against [`STANDARDS`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::standards::named_bandwidth;
+///
+/// assert_eq!(named_bandwidth("100GbE"), Some(Bandwidth::new(100, 0)));
+/// assert_eq!(named_bandwidth("oc-48"), Some(Bandwidth::new(2, 488_320_000)));
+/// assert_eq!(named_bandwidth("unknown"), None);
+/// ```
+pub fn named_bandwidth(name: &str) -> Option<Bandwidth> {
+    STANDARDS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, bw)| *bw)
+}
+
+/// Returns the canonical name of the standard with exactly this bandwidth
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::standards::bandwidth_name;
+///
+/// assert_eq!(bandwidth_name(Bandwidth::new(25, 0)), Some("25GbE"));
+/// assert_eq!(bandwidth_name(Bandwidth::new(25, 1)), None);
+/// ```
+pub fn bandwidth_name(bw: Bandwidth) -> Option<&'static str> {
+    STANDARDS
+        .iter()
+        .find(|(_, b)| *b == bw)
+        .map(|(name, _)| *name)
+}
+
+/// A common link speed, for use in code instead of a raw [`Bandwidth`]
+///
+/// The InfiniBand speeds are the nominal rates of a 4x link.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::standards::StandardNetworkSpeed;
+///
+/// assert_eq!(
+///     StandardNetworkSpeed::FastEthernet100M.bandwidth(),
+///     Bandwidth::from_mbps(100)
+/// );
+/// assert_eq!(
+///     StandardNetworkSpeed::closest(Bandwidth::from_mbps(9_950)),
+///     Some(StandardNetworkSpeed::TenGigabit)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StandardNetworkSpeed {
+    /// Ethernet, 10Mbps
+    Ethernet10M,
+    /// Fast Ethernet, 100Mbps
+    FastEthernet100M,
+    /// Gigabit Ethernet, 1Gbps
+    GigabitEthernet,
+    /// 2.5 Gigabit Ethernet, 2.5Gbps
+    TwoPointFiveGigabit,
+    /// 5 Gigabit Ethernet, 5Gbps
+    FiveGigabit,
+    /// 10 Gigabit Ethernet, 10Gbps
+    TenGigabit,
+    /// 25 Gigabit Ethernet, 25Gbps
+    TwentyFiveGigabit,
+    /// 40 Gigabit Ethernet, 40Gbps
+    FortyGigabit,
+    /// 50 Gigabit Ethernet, 50Gbps
+    FiftyGigabit,
+    /// 100 Gigabit Ethernet, 100Gbps
+    HundredGigabit,
+    /// 200 Gigabit Ethernet, 200Gbps
+    TwoHundredGigabit,
+    /// 400 Gigabit Ethernet, 400Gbps
+    FourHundredGigabit,
+    /// 800 Gigabit Ethernet, 800Gbps
+    EightHundredGigabit,
+    /// 1Tbps
+    Terabit,
+    /// InfiniBand single data rate, 10Gbps
+    InfiniBandSDR,
+    /// InfiniBand double data rate, 20Gbps
+    InfiniBandDDR,
+    /// InfiniBand quad data rate, 40Gbps
+    InfiniBandQDR,
+    /// InfiniBand fourteen data rate, 56Gbps
+    InfiniBandFDR,
+    /// InfiniBand enhanced data rate, 100Gbps
+    InfiniBandEDR,
+    /// InfiniBand high data rate, 200Gbps
+    InfiniBandHDR,
+    /// InfiniBand next data rate, 400Gbps
+    InfiniBandNDR,
+}
+
+impl StandardNetworkSpeed {
+    /// All speeds, Ethernet first, each group from slowest to fastest
+    pub const ALL: [StandardNetworkSpeed; 21] = [
+        StandardNetworkSpeed::Ethernet10M,
+        StandardNetworkSpeed::FastEthernet100M,
+        StandardNetworkSpeed::GigabitEthernet,
+        StandardNetworkSpeed::TwoPointFiveGigabit,
+        StandardNetworkSpeed::FiveGigabit,
+        StandardNetworkSpeed::TenGigabit,
+        StandardNetworkSpeed::TwentyFiveGigabit,
+        StandardNetworkSpeed::FortyGigabit,
+        StandardNetworkSpeed::FiftyGigabit,
+        StandardNetworkSpeed::HundredGigabit,
+        StandardNetworkSpeed::TwoHundredGigabit,
+        StandardNetworkSpeed::FourHundredGigabit,
+        StandardNetworkSpeed::EightHundredGigabit,
+        StandardNetworkSpeed::Terabit,
+        StandardNetworkSpeed::InfiniBandSDR,
+        StandardNetworkSpeed::InfiniBandDDR,
+        StandardNetworkSpeed::InfiniBandQDR,
+        StandardNetworkSpeed::InfiniBandFDR,
+        StandardNetworkSpeed::InfiniBandEDR,
+        StandardNetworkSpeed::InfiniBandHDR,
+        StandardNetworkSpeed::InfiniBandNDR,
+    ];
+
+    /// Returns the bandwidth of this speed
+    pub const fn bandwidth(self) -> Bandwidth {
+        match self {
+            StandardNetworkSpeed::Ethernet10M => Bandwidth::new(0, 10_000_000),
+            StandardNetworkSpeed::FastEthernet100M => Bandwidth::new(0, 100_000_000),
+            StandardNetworkSpeed::GigabitEthernet => Bandwidth::new(1, 0),
+            StandardNetworkSpeed::TwoPointFiveGigabit => Bandwidth::new(2, 500_000_000),
+            StandardNetworkSpeed::FiveGigabit => Bandwidth::new(5, 0),
+            StandardNetworkSpeed::TenGigabit => Bandwidth::new(10, 0),
+            StandardNetworkSpeed::TwentyFiveGigabit => Bandwidth::new(25, 0),
+            StandardNetworkSpeed::FortyGigabit => Bandwidth::new(40, 0),
+            StandardNetworkSpeed::FiftyGigabit => Bandwidth::new(50, 0),
+            StandardNetworkSpeed::HundredGigabit => Bandwidth::new(100, 0),
+            StandardNetworkSpeed::TwoHundredGigabit => Bandwidth::new(200, 0),
+            StandardNetworkSpeed::FourHundredGigabit => Bandwidth::new(400, 0),
+            StandardNetworkSpeed::EightHundredGigabit => Bandwidth::new(800, 0),
+            StandardNetworkSpeed::Terabit => Bandwidth::new(1_000, 0),
+            StandardNetworkSpeed::InfiniBandSDR => Bandwidth::new(10, 0),
+            StandardNetworkSpeed::InfiniBandDDR => Bandwidth::new(20, 0),
+            StandardNetworkSpeed::InfiniBandQDR => Bandwidth::new(40, 0),
+            StandardNetworkSpeed::InfiniBandFDR => Bandwidth::new(56, 0),
+            StandardNetworkSpeed::InfiniBandEDR => Bandwidth::new(100, 0),
+            StandardNetworkSpeed::InfiniBandHDR => Bandwidth::new(200, 0),
+            StandardNetworkSpeed::InfiniBandNDR => Bandwidth::new(400, 0),
+        }
+    }
+
+    /// Returns the speed nearest to `bw`, if it is within 1% of it
+    ///
+    /// Speeds shared by Ethernet and InfiniBand, like 40Gbps, resolve to
+    /// the Ethernet one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::standards::StandardNetworkSpeed;
+    ///
+    /// let speed = StandardNetworkSpeed::closest(Bandwidth::new(56, 300_000_000));
+    /// assert_eq!(speed, Some(StandardNetworkSpeed::InfiniBandFDR));
+    /// assert_eq!(StandardNetworkSpeed::closest(Bandwidth::from_gbps(15)), None);
+    /// ```
+    pub fn closest(bw: Bandwidth) -> Option<StandardNetworkSpeed> {
+        let bps = bw.as_bps();
+        let mut best: Option<(u128, StandardNetworkSpeed)> = None;
+        for speed in StandardNetworkSpeed::ALL {
+            let target = speed.bandwidth().as_bps();
+            let diff = bps.abs_diff(target);
+            if diff * 100 <= target && best.map_or(true, |(best, _)| diff < best) {
+                best = Some((diff, speed));
+            }
+        }
+        best.map(|(_, speed)| speed)
+    }
+}
+
+/// Same as [`StandardNetworkSpeed::bandwidth`]
+impl From<StandardNetworkSpeed> for Bandwidth {
+    fn from(speed: StandardNetworkSpeed) -> Bandwidth {
+        speed.bandwidth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_bandwidth() {
+        assert_eq!(named_bandwidth("10GbE"), Some(Bandwidth::new(10, 0)));
+        assert_eq!(named_bandwidth("25GbE"), Some(Bandwidth::new(25, 0)));
+        assert_eq!(named_bandwidth("100GbE"), Some(Bandwidth::new(100, 0)));
+        assert_eq!(named_bandwidth("400gbe"), Some(Bandwidth::new(400, 0)));
+        assert_eq!(
+            named_bandwidth("OC-3"),
+            Some(Bandwidth::new(0, 155_520_000))
+        );
+        assert_eq!(
+            named_bandwidth("OC-12"),
+            Some(Bandwidth::new(0, 622_080_000))
+        );
+        assert_eq!(
+            named_bandwidth("OC-48"),
+            Some(Bandwidth::new(2, 488_320_000))
+        );
+        assert_eq!(
+            named_bandwidth("OC-192"),
+            Some(Bandwidth::new(9, 953_280_000))
+        );
+        assert_eq!(named_bandwidth("T1"), Some(Bandwidth::new(0, 1_544_000)));
+        assert_eq!(named_bandwidth("T3"), Some(Bandwidth::new(0, 44_736_000)));
+        assert_eq!(named_bandwidth("DS0"), Some(Bandwidth::new(0, 64_000)));
+        assert_eq!(named_bandwidth("DS1"), Some(Bandwidth::new(0, 1_544_000)));
+        assert_eq!(named_bandwidth(""), None);
+        assert_eq!(named_bandwidth("10 GbE"), None);
+    }
+
+    #[test]
+    fn test_bandwidth_name() {
+        assert_eq!(bandwidth_name(Bandwidth::new(0, 10_000_000)), Some("10MbE"));
+        assert_eq!(bandwidth_name(Bandwidth::new(1, 0)), Some("1GbE"));
+        assert_eq!(bandwidth_name(Bandwidth::new(10, 0)), Some("10GbE"));
+        assert_eq!(bandwidth_name(Bandwidth::new(100, 0)), Some("100GbE"));
+        assert_eq!(bandwidth_name(Bandwidth::new(0, 155_520_000)), Some("OC-3"));
+        assert_eq!(bandwidth_name(Bandwidth::new(0, 64_000)), Some("DS0"));
+        // Shared rates resolve to the first name in the table
+        assert_eq!(bandwidth_name(Bandwidth::new(0, 1_544_000)), Some("T1"));
+        assert_eq!(bandwidth_name(Bandwidth::new(0, 44_736_000)), Some("T3"));
+        assert_eq!(bandwidth_name(Bandwidth::new(0, 0)), None);
+        assert_eq!(bandwidth_name(Bandwidth::new(10, 1)), None);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for (name, bw) in STANDARDS {
+            assert_eq!(named_bandwidth(name), Some(*bw));
+            let canonical = bandwidth_name(*bw).unwrap();
+            assert_eq!(named_bandwidth(canonical), Some(*bw));
+        }
+    }
+
+    #[test]
+    fn test_speed_bandwidth() {
+        use StandardNetworkSpeed::*;
+        assert_eq!(Ethernet10M.bandwidth(), Bandwidth::from_mbps(10));
+        assert_eq!(GigabitEthernet.bandwidth(), Bandwidth::from_gbps(1));
+        assert_eq!(TwoPointFiveGigabit.bandwidth(), Bandwidth::from_mbps(2_500));
+        assert_eq!(FourHundredGigabit.bandwidth(), Bandwidth::from_gbps(400));
+        assert_eq!(Terabit.bandwidth(), Bandwidth::from_gbps(1_000));
+        assert_eq!(InfiniBandSDR.bandwidth(), Bandwidth::from_gbps(10));
+        assert_eq!(InfiniBandHDR.bandwidth(), Bandwidth::from_gbps(200));
+        assert_eq!(Bandwidth::from(HundredGigabit), Bandwidth::from_gbps(100));
+        // the Ethernet speeds agree with the table
+        for speed in &StandardNetworkSpeed::ALL[..13] {
+            assert!(bandwidth_name(speed.bandwidth()).unwrap().ends_with("bE"));
+        }
+    }
+
+    #[test]
+    fn test_closest() {
+        use StandardNetworkSpeed::*;
+        for speed in StandardNetworkSpeed::ALL {
+            let found = StandardNetworkSpeed::closest(speed.bandwidth()).unwrap();
+            assert_eq!(found.bandwidth(), speed.bandwidth());
+        }
+        assert_eq!(
+            StandardNetworkSpeed::closest(Bandwidth::from_gbps(40)),
+            Some(FortyGigabit)
+        );
+        assert_eq!(
+            StandardNetworkSpeed::closest(Bandwidth::from_mbps(99)),
+            Some(FastEthernet100M)
+        );
+        assert_eq!(
+            StandardNetworkSpeed::closest(Bandwidth::from_mbps(101)),
+            Some(FastEthernet100M)
+        );
+        assert_eq!(
+            StandardNetworkSpeed::closest(Bandwidth::from_bps(101_000_001)),
+            None
+        );
+        assert_eq!(
+            StandardNetworkSpeed::closest(Bandwidth::from_kbps(98_999)),
+            None
+        );
+        assert_eq!(
+            StandardNetworkSpeed::closest(Bandwidth::from_gbps(995)),
+            Some(Terabit)
+        );
+        assert_eq!(StandardNetworkSpeed::closest(Bandwidth::ZERO), None);
+        assert_eq!(StandardNetworkSpeed::closest(Bandwidth::MAX), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Suggestions for misspelled bandwidth units
+
+use crate::BIT_UNITS as UNITS;
+use alloc::vec::Vec;
+
+/// Levenshtein distance between two strings, counted in chars
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Returns the known unit closest to an unknown one, if any is close enough
+///
+/// Units are compared case-insensitively. A unit which the input starts with
+/// (`Gbps/s`), or which starts with the input (`Gbit`), is preferred;
+/// otherwise a unit differing by a single character is picked.
+///
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::suggest_unit;
+///
+/// assert_eq!(suggest_unit("Gbps/s"), Some("Gbps"));
+/// assert_eq!(suggest_unit("Gbit"), Some("Gbit/s"));
+/// assert_eq!(suggest_unit("MBPS"), Some("Mbps"));
+/// assert_eq!(suggest_unit("byte/s"), None);
+/// ```
+pub fn suggest_unit(invalid: &str) -> Option<&'static str> {
+    if invalid.is_empty() {
+        return None;
+    }
+    let invalid = invalid.to_lowercase();
+    let len = invalid.chars().count();
+    let mut best: Option<(bool, usize, &'static str)> = None;
+    for &unit in UNITS {
+        let lower = unit.to_lowercase();
+        let prefix = len >= 2 && (invalid.starts_with(&lower) || lower.starts_with(&invalid));
+        let dist = distance(&invalid, &lower);
+        if !prefix && (dist > 1 || dist >= len) {
+            continue;
+        }
+        let better = match best {
+            None => true,
+            Some((best_prefix, best_dist, _)) => (!prefix, dist) < (!best_prefix, best_dist),
+        };
+        if better {
+            best = Some((prefix, dist, unit));
+        }
+    }
+    best.map(|(_, _, unit)| unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("gbps", "gbps"), 0);
+        assert_eq!(distance("gbit", "gbps"), 2);
+        assert_eq!(distance("byte/s", "b/s"), 3);
+        assert_eq!(distance("", "bps"), 3);
+    }
+
+    #[test]
+    fn test_suggest_unit() {
+        assert_eq!(suggest_unit("Gbps/s"), Some("Gbps"));
+        assert_eq!(suggest_unit("Gbit"), Some("Gbit/s"));
+        assert_eq!(suggest_unit("gbit"), Some("Gbit/s"));
+        assert_eq!(suggest_unit("GBPS"), Some("Gbps"));
+        assert_eq!(suggest_unit("Mbs"), Some("Mbps"));
+        assert_eq!(suggest_unit("kbs"), Some("kbps"));
+        assert_eq!(suggest_unit("kpbs"), None);
+        assert_eq!(suggest_unit("Tbit/sec"), Some("Tbit/s"));
+        assert_eq!(suggest_unit("byte/s"), None);
+        assert_eq!(suggest_unit("s"), None);
+        assert_eq!(suggest_unit("min"), None);
+        assert_eq!(suggest_unit(""), None);
+    }
+}
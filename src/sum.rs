@@ -0,0 +1,170 @@
+//! Overflow-aware summation of bandwidths
+
+use crate::Error;
+use bandwidth::Bandwidth;
+use core::iter::{FromIterator, Sum};
+
+/// An accumulator adding up bandwidths without panicking on overflow
+///
+/// Summing with [`Iterator::sum`] into a `Bandwidth` panics when the total
+/// overflows. `BandwidthSum` instead remembers the overflow, saturating the
+/// total at [`Bandwidth::MAX`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::BandwidthSum;
+///
+/// let sum: BandwidthSum = [Bandwidth::from_gbps(1), Bandwidth::from_mbps(500)]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(sum.total(), Bandwidth::new(1, 500_000_000));
+///
+/// let sum = BandwidthSum::try_from_iter([Bandwidth::MAX, Bandwidth::from_bps(1)]);
+/// assert!(sum.is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BandwidthSum {
+    total: Bandwidth,
+    overflowed: bool,
+}
+
+impl BandwidthSum {
+    /// Creates an empty sum
+    pub fn new() -> BandwidthSum {
+        BandwidthSum::default()
+    }
+
+    /// Adds `bw` to the sum, returning `None` if the total overflowed
+    ///
+    /// Once the total has overflowed it stays at [`Bandwidth::MAX`] and every
+    /// further call returns `None`.
+    pub fn add(&mut self, bw: Bandwidth) -> Option<()> {
+        match self.total.checked_add(bw) {
+            Some(total) if !self.overflowed => {
+                self.total = total;
+                Some(())
+            }
+            _ => {
+                self.total = Bandwidth::MAX;
+                self.overflowed = true;
+                None
+            }
+        }
+    }
+
+    /// Returns the total, saturated at [`Bandwidth::MAX`] on overflow
+    pub fn total(&self) -> Bandwidth {
+        self.total
+    }
+
+    /// Returns the total, or `None` if it overflowed
+    pub fn checked_total(&self) -> Option<Bandwidth> {
+        if self.overflowed {
+            None
+        } else {
+            Some(self.total)
+        }
+    }
+
+    /// Returns `true` if the total overflowed
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// Sums all bandwidths of `iter`, failing with [`Error::NumberOverflow`]
+    /// if the total overflows
+    ///
+    /// Iteration stops at the first overflow.
+    pub fn try_from_iter<I>(iter: I) -> Result<BandwidthSum, Error>
+    where
+        I: IntoIterator<Item = Bandwidth>,
+    {
+        let mut sum = BandwidthSum::new();
+        for bw in iter {
+            sum.add(bw).ok_or(Error::NumberOverflow)?;
+        }
+        Ok(sum)
+    }
+}
+
+impl FromIterator<Bandwidth> for BandwidthSum {
+    fn from_iter<I: IntoIterator<Item = Bandwidth>>(iter: I) -> BandwidthSum {
+        let mut sum = BandwidthSum::new();
+        for bw in iter {
+            if sum.add(bw).is_none() {
+                break;
+            }
+        }
+        sum
+    }
+}
+
+impl Sum<Bandwidth> for BandwidthSum {
+    fn sum<I: Iterator<Item = Bandwidth>>(iter: I) -> BandwidthSum {
+        iter.collect()
+    }
+}
+
+impl<'a> Sum<&'a Bandwidth> for BandwidthSum {
+    fn sum<I: Iterator<Item = &'a Bandwidth>>(iter: I) -> BandwidthSum {
+        iter.copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let bws = vec![Bandwidth::from_gbps(1); 10];
+        let sum: BandwidthSum = bws.iter().sum();
+        assert_eq!(sum.total(), Bandwidth::from_gbps(10));
+        assert_eq!(sum.checked_total(), Some(Bandwidth::from_gbps(10)));
+        assert_eq!(
+            BandwidthSum::try_from_iter(bws).map(|s| s.total()),
+            Ok(Bandwidth::from_gbps(10))
+        );
+        assert_eq!(BandwidthSum::new().total(), Bandwidth::ZERO);
+    }
+
+    #[test]
+    fn test_sum_many() {
+        let bws: Vec<Bandwidth> = (0..1000u64)
+            .map(|i| Bandwidth::new(i % 7, (i * 999_983 % 1_000_000_000) as u32))
+            .collect();
+        let mut expected = Bandwidth::ZERO;
+        for bw in &bws {
+            expected = expected.checked_add(*bw).unwrap();
+        }
+        assert_eq!(bws.iter().sum::<BandwidthSum>().total(), expected);
+        assert_eq!(bws.iter().sum::<Bandwidth>(), expected);
+        assert_eq!(
+            BandwidthSum::try_from_iter(bws).map(|s| s.checked_total()),
+            Ok(Some(expected))
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let bws = [
+            Bandwidth::new(u64::MAX, 0),
+            Bandwidth::new(0, 999_999_999),
+            Bandwidth::from_bps(1),
+        ];
+        assert_eq!(BandwidthSum::try_from_iter(bws), Err(Error::NumberOverflow));
+
+        let sum: BandwidthSum = bws.iter().copied().sum();
+        assert!(sum.overflowed());
+        assert_eq!(sum.total(), Bandwidth::MAX);
+        assert_eq!(sum.checked_total(), None);
+
+        let mut sum = BandwidthSum::new();
+        assert_eq!(sum.add(Bandwidth::MAX), Some(()));
+        assert_eq!(sum.add(Bandwidth::from_bps(1)), None);
+        assert_eq!(sum.add(Bandwidth::ZERO), None);
+        assert_eq!(sum.total(), Bandwidth::MAX);
+    }
+}
@@ -0,0 +1,160 @@
+//! Assertions for tests involving bandwidths
+//!
+//! Only available with the `testing-utils` feature.
+//!
+//! # Example
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::{assert_bandwidth_approx_eq, assert_bandwidth_lt};
+//!
+//! assert_bandwidth_approx_eq!(Bandwidth::from_bps(1_000), Bandwidth::from_bps(1_002), 2);
+//! assert_bandwidth_lt!(Bandwidth::from_mbps(100), Bandwidth::from_gbps(1));
+//! ```
+
+use bandwidth::Bandwidth;
+
+/// Comparison of bandwidths with a tolerance
+pub trait BandwidthApproxEq {
+    /// Returns `true` if `self` and `other` differ by at most
+    /// `tolerance_bps` bits per second, in either direction
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::testing::BandwidthApproxEq;
+    ///
+    /// assert!(Bandwidth::from_bps(1_000).approx_eq(Bandwidth::from_bps(999), 1));
+    /// assert!(!Bandwidth::from_bps(1_000).approx_eq(Bandwidth::from_bps(998), 1));
+    /// ```
+    fn approx_eq(self, other: Bandwidth, tolerance_bps: u64) -> bool;
+}
+
+impl BandwidthApproxEq for Bandwidth {
+    fn approx_eq(self, other: Bandwidth, tolerance_bps: u64) -> bool {
+        self.as_bps().abs_diff(other.as_bps()) <= tolerance_bps as u128
+    }
+}
+
+impl BandwidthApproxEq for crate::HumanBandwidth {
+    fn approx_eq(self, other: Bandwidth, tolerance_bps: u64) -> bool {
+        (*self).approx_eq(other, tolerance_bps)
+    }
+}
+
+/// Asserts that two bandwidths differ by at most `tol` bits per second
+///
+/// See [`BandwidthApproxEq`][crate::testing::BandwidthApproxEq]. The failure
+/// message shows both values in human-readable form.
+#[macro_export]
+macro_rules! assert_bandwidth_approx_eq {
+    ($a:expr, $b:expr, $tol:expr $(,)?) => {{
+        let (a, b, tol): ($crate::re::bandwidth::Bandwidth, $crate::re::bandwidth::Bandwidth, u64) =
+            ($a, $b, $tol);
+        if !$crate::testing::BandwidthApproxEq::approx_eq(a, b, tol) {
+            panic!(
+                "assertion failed: `{} ≈ {}` (tolerance {}bps)\n  left: `{}` ({:?})\n right: `{}` ({:?})",
+                stringify!($a),
+                stringify!($b),
+                tol,
+                $crate::format_bandwidth(a),
+                a,
+                $crate::format_bandwidth(b),
+                b,
+            );
+        }
+    }};
+}
+
+/// Asserts that the first bandwidth is greater than the second
+///
+/// The failure message shows both values in human-readable form.
+#[macro_export]
+macro_rules! assert_bandwidth_gt {
+    ($a:expr, $b:expr $(,)?) => {{
+        let (a, b): (
+            $crate::re::bandwidth::Bandwidth,
+            $crate::re::bandwidth::Bandwidth,
+        ) = ($a, $b);
+        if !(a > b) {
+            panic!(
+                "assertion failed: `{} > {}`\n  left: `{}`\n right: `{}`",
+                stringify!($a),
+                stringify!($b),
+                $crate::format_bandwidth(a),
+                $crate::format_bandwidth(b),
+            );
+        }
+    }};
+}
+
+/// Asserts that the first bandwidth is less than the second
+///
+/// The failure message shows both values in human-readable form.
+#[macro_export]
+macro_rules! assert_bandwidth_lt {
+    ($a:expr, $b:expr $(,)?) => {{
+        let (a, b): (
+            $crate::re::bandwidth::Bandwidth,
+            $crate::re::bandwidth::Bandwidth,
+        ) = ($a, $b);
+        if !(a < b) {
+            panic!(
+                "assertion failed: `{} < {}`\n  left: `{}`\n right: `{}`",
+                stringify!($a),
+                stringify!($b),
+                $crate::format_bandwidth(a),
+                $crate::format_bandwidth(b),
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn test_approx_eq() {
+        let bw = Bandwidth::new(1, 0);
+        assert!(bw.approx_eq(bw, 0));
+        assert!(bw.approx_eq(Bandwidth::new(0, 999_999_990), 10));
+        assert!(Bandwidth::new(0, 999_999_990).approx_eq(bw, 10));
+        assert!(!bw.approx_eq(Bandwidth::new(1, 11), 10));
+        assert!(Bandwidth::ZERO.approx_eq(Bandwidth::from_bps(u64::MAX), u64::MAX));
+        assert!(!Bandwidth::ZERO.approx_eq(Bandwidth::MAX, u64::MAX));
+        assert!(crate::HumanBandwidth::from(bw).approx_eq(bw, 0));
+    }
+
+    #[test]
+    fn test_macros() {
+        assert_bandwidth_approx_eq!(Bandwidth::from_bps(5), Bandwidth::from_bps(7), 2);
+        assert_bandwidth_gt!(Bandwidth::from_gbps(1), Bandwidth::from_mbps(999));
+        assert_bandwidth_lt!(Bandwidth::from_mbps(999), Bandwidth::from_gbps(1));
+
+        let err = catch_unwind(|| {
+            assert_bandwidth_approx_eq!(Bandwidth::from_mbps(1), Bandwidth::from_mbps(2), 10);
+        })
+        .unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("tolerance 10bps"), "{}", msg);
+        assert!(
+            msg.contains("`1Mbps`") && msg.contains("`2Mbps`"),
+            "{}",
+            msg
+        );
+
+        let err = catch_unwind(|| {
+            assert_bandwidth_gt!(Bandwidth::from_kbps(1), Bandwidth::from_kbps(1));
+        })
+        .unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("left: `1kbps`"), "{}", msg);
+
+        assert!(catch_unwind(|| {
+            assert_bandwidth_lt!(Bandwidth::from_kbps(2), Bandwidth::from_kbps(1));
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! Network calculations with bandwidths
+
+use crate::{BandwidthExt, BandwidthUnit};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+use bandwidth::Bandwidth;
+use core::time::Duration;
+
+/// Exact bandwidth-delay product in bits, `None` on `u128` overflow
+fn delay_product_bits(bw: Bandwidth, rtt: Duration) -> Option<u128> {
+    Some(bw.as_bps().checked_mul(rtt.as_nanos())? / 1_000_000_000)
+}
+
+/// Returns the bandwidth-delay product of `bw` and `rtt` in bits
+///
+/// This is the amount of data in flight on a path with bandwidth `bw` and
+/// round-trip time `rtt`. The product is computed exactly and truncated to
+/// whole bits; `None` is returned if it does not fit in a `u64`.
+pub fn bandwidth_delay_product_bits(bw: Bandwidth, rtt: Duration) -> Option<u64> {
+    u64::try_from(delay_product_bits(bw, rtt)?).ok()
+}
+
+/// Returns the bandwidth-delay product of `bw` and `rtt` in bytes
+///
+/// This is the TCP buffer (window) size needed to keep a path with bandwidth
+/// `bw` and round-trip time `rtt` busy. Partial bytes are truncated, and
+/// `None` is returned if the result does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::util::bandwidth_delay_product_bytes;
+/// use std::time::Duration;
+///
+/// let bdp = bandwidth_delay_product_bytes(Bandwidth::new(1, 0), Duration::from_millis(100));
+/// assert_eq!(bdp, Some(12_500_000));
+/// ```
+pub fn bandwidth_delay_product_bytes(bw: Bandwidth, rtt: Duration) -> Option<u64> {
+    u64::try_from(delay_product_bits(bw, rtt)? / 8).ok()
+}
+
+/// Returns how long it takes to transfer `size_bytes` at `rate`
+///
+/// The time is rounded up to whole nanoseconds. `None` is returned if `rate`
+/// is zero, or if the time does not fit in a [`Duration`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::util::estimated_transfer_time;
+/// use std::time::Duration;
+///
+/// let time = estimated_transfer_time(1_000_000_000, Bandwidth::from_gbps(1));
+/// assert_eq!(time, Some(Duration::from_secs(8)));
+/// assert_eq!(estimated_transfer_time(1, Bandwidth::ZERO), None);
+/// ```
+pub fn estimated_transfer_time(size_bytes: u64, rate: Bandwidth) -> Option<Duration> {
+    let bps = rate.as_bps();
+    if bps == 0 {
+        return None;
+    }
+    let bits = size_bytes as u128 * 8 * 1_000_000_000;
+    let nanos = (bits + bps - 1) / bps;
+    let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+    Some(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+/// Returns the number of bytes transferred at `rate` during `duration`
+///
+/// Partial bytes are truncated, and the result saturates at `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::util::transfer_size;
+/// use std::time::Duration;
+///
+/// assert_eq!(transfer_size(Duration::from_secs(5), Bandwidth::from_mbps(100)), 62_500_000);
+/// ```
+pub fn transfer_size(duration: Duration, rate: Bandwidth) -> u64 {
+    rate.as_bps()
+        .checked_mul(duration.as_nanos())
+        .map(|bits| bits / 8_000_000_000)
+        .and_then(|bytes| u64::try_from(bytes).ok())
+        .unwrap_or(u64::MAX)
+}
+
+/// Rounds `bw` down to a whole multiple of `unit`
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::util::bandwidth_floor;
+/// use human_bandwidth::BandwidthUnit;
+///
+/// let bw = Bandwidth::from_kbps(1_999);
+/// assert_eq!(bandwidth_floor(bw, BandwidthUnit::Mbps), Bandwidth::from_mbps(1));
+/// ```
+pub fn bandwidth_floor(bw: Bandwidth, unit: BandwidthUnit) -> Bandwidth {
+    let scale = unit.multiplier_bps() as u128;
+    Bandwidth::from_bps_u128(bw.as_bps() / scale * scale).unwrap_or(bw)
+}
+
+/// Rounds `bw` up to a whole multiple of `unit`, `None` if the result
+/// overflows a [`Bandwidth`]
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::util::bandwidth_ceil;
+/// use human_bandwidth::BandwidthUnit;
+///
+/// let bw = Bandwidth::from_kbps(1_001);
+/// assert_eq!(bandwidth_ceil(bw, BandwidthUnit::Mbps), Some(Bandwidth::from_mbps(2)));
+/// assert_eq!(bandwidth_ceil(Bandwidth::MAX, BandwidthUnit::Gbps), None);
+/// ```
+pub fn bandwidth_ceil(bw: Bandwidth, unit: BandwidthUnit) -> Option<Bandwidth> {
+    let scale = unit.multiplier_bps() as u128;
+    Bandwidth::from_bps_u128((bw.as_bps() + scale - 1) / scale * scale)
+}
+
+/// Rounds `bw` to the nearest multiple of `unit`, rounding half up
+///
+/// If rounding up overflows a [`Bandwidth`], `bw` is rounded down instead,
+/// so the result is always the nearest multiple that fits.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::util::bandwidth_round;
+/// use human_bandwidth::BandwidthUnit;
+///
+/// let round = |kbps| bandwidth_round(Bandwidth::from_kbps(kbps), BandwidthUnit::Mbps);
+/// assert_eq!(round(1_499), Bandwidth::from_mbps(1));
+/// assert_eq!(round(1_500), Bandwidth::from_mbps(2));
+/// ```
+pub fn bandwidth_round(bw: Bandwidth, unit: BandwidthUnit) -> Bandwidth {
+    let scale = unit.multiplier_bps() as u128;
+    Bandwidth::from_bps_u128((bw.as_bps() + scale / 2) / scale * scale)
+        .unwrap_or_else(|| bandwidth_floor(bw, unit))
+}
+
+/// Formats a byte count with SI prefixes and one fractional digit, e.g.
+/// `12.5 MB`
+///
+/// The fraction is rounded half up and left out when it is zero. Like the
+/// bandwidth units, the prefixes are powers of 1000.
+///
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::util::format_bytes_human;
+///
+/// assert_eq!(format_bytes_human(12_500_000), "12.5 MB");
+/// assert_eq!(format_bytes_human(64_000), "64 kB");
+/// assert_eq!(format_bytes_human(512), "512 B");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn format_bytes_human(bytes: u64) -> String {
+    const UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+    let bytes = bytes as u128;
+    let mut exp = 0;
+    while exp + 1 < UNITS.len() && bytes >= 1000u128.pow(exp as u32 + 1) {
+        exp += 1;
+    }
+    let scale = 1000u128.pow(exp as u32);
+    let mut tenths = (bytes * 10 + scale / 2) / scale;
+    if tenths >= 10_000 && exp + 1 < UNITS.len() {
+        exp += 1;
+        tenths /= 1000;
+    }
+    if tenths % 10 == 0 {
+        format!("{} {}", tenths / 10, UNITS[exp])
+    } else {
+        format!("{}.{} {}", tenths / 10, tenths % 10, UNITS[exp])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bandwidth_delay_product() {
+        let rtt = Duration::from_millis(100);
+        assert_eq!(
+            bandwidth_delay_product_bytes(Bandwidth::new(1, 0), rtt),
+            Some(12_500_000)
+        );
+        assert_eq!(
+            bandwidth_delay_product_bits(Bandwidth::new(1, 0), rtt),
+            Some(100_000_000)
+        );
+        assert_eq!(
+            bandwidth_delay_product_bytes(Bandwidth::from_mbps(10), Duration::from_micros(1_500)),
+            Some(1_875)
+        );
+        assert_eq!(
+            bandwidth_delay_product_bits(Bandwidth::from_bps(7), Duration::from_millis(500)),
+            Some(3)
+        );
+        assert_eq!(
+            bandwidth_delay_product_bytes(Bandwidth::ZERO, Duration::MAX),
+            Some(0)
+        );
+        assert_eq!(
+            bandwidth_delay_product_bytes(Bandwidth::MAX, Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(
+            bandwidth_delay_product_bits(Bandwidth::MAX, Duration::MAX),
+            None
+        );
+        assert_eq!(
+            bandwidth_delay_product_bits(Bandwidth::from_bps(u64::MAX), Duration::from_secs(1)),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            bandwidth_delay_product_bits(Bandwidth::from_bps(u64::MAX), Duration::from_secs(2)),
+            None
+        );
+        assert_eq!(
+            bandwidth_delay_product_bytes(Bandwidth::from_bps(u64::MAX), Duration::from_secs(2)),
+            Some(u64::MAX / 4)
+        );
+    }
+
+    #[test]
+    fn test_estimated_transfer_time() {
+        let gbps = Bandwidth::from_gbps(1);
+        assert_eq!(
+            estimated_transfer_time(125_000_000, gbps),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            estimated_transfer_time(1_500, gbps),
+            Some(Duration::from_micros(12))
+        );
+        assert_eq!(
+            estimated_transfer_time(1, Bandwidth::from_bps(3)),
+            Some(Duration::new(2, 666_666_667))
+        );
+        assert_eq!(estimated_transfer_time(0, gbps), Some(Duration::ZERO));
+        assert_eq!(estimated_transfer_time(0, Bandwidth::ZERO), None);
+        assert_eq!(estimated_transfer_time(u64::MAX, Bandwidth::ZERO), None);
+        assert_eq!(
+            estimated_transfer_time(u64::MAX, Bandwidth::from_bps(8)),
+            Some(Duration::from_secs(u64::MAX))
+        );
+        assert_eq!(
+            estimated_transfer_time(u64::MAX, Bandwidth::from_bps(1)),
+            None
+        );
+        assert_eq!(
+            estimated_transfer_time(u64::MAX, Bandwidth::MAX),
+            Some(Duration::from_nanos(8))
+        );
+    }
+
+    #[test]
+    fn test_transfer_size() {
+        let gbps = Bandwidth::from_gbps(1);
+        assert_eq!(transfer_size(Duration::from_secs(1), gbps), 125_000_000);
+        assert_eq!(transfer_size(Duration::from_micros(12), gbps), 1_500);
+        assert_eq!(
+            transfer_size(Duration::from_secs(1), Bandwidth::from_bps(7)),
+            0
+        );
+        assert_eq!(transfer_size(Duration::MAX, Bandwidth::ZERO), 0);
+        assert_eq!(transfer_size(Duration::MAX, Bandwidth::MAX), u64::MAX);
+        for size in [1, 1_500, 125_000_000, 1 << 40] {
+            let time = estimated_transfer_time(size, gbps).unwrap();
+            assert_eq!(transfer_size(time, gbps), size);
+        }
+    }
+
+    #[test]
+    fn test_bandwidth_floor() {
+        let bw = Bandwidth::new(1_234, 567_891_234);
+        let floor = |unit| bandwidth_floor(bw, unit);
+        assert_eq!(floor(BandwidthUnit::Bps), bw);
+        assert_eq!(
+            floor(BandwidthUnit::Kbps),
+            Bandwidth::new(1_234, 567_891_000)
+        );
+        assert_eq!(
+            floor(BandwidthUnit::Mbps),
+            Bandwidth::new(1_234, 567_000_000)
+        );
+        assert_eq!(floor(BandwidthUnit::Gbps), Bandwidth::new(1_234, 0));
+        assert_eq!(floor(BandwidthUnit::Tbps), Bandwidth::new(1_000, 0));
+        assert_eq!(floor(BandwidthUnit::Pbps), Bandwidth::ZERO);
+        assert_eq!(
+            bandwidth_floor(Bandwidth::from_mbps(5), BandwidthUnit::Mbps),
+            Bandwidth::from_mbps(5)
+        );
+        assert_eq!(
+            bandwidth_floor(Bandwidth::ZERO, BandwidthUnit::Tbps),
+            Bandwidth::ZERO
+        );
+        assert_eq!(
+            bandwidth_floor(Bandwidth::MAX, BandwidthUnit::Gbps),
+            Bandwidth::new(u64::MAX, 0)
+        );
+    }
+
+    #[test]
+    fn test_bandwidth_ceil() {
+        let bw = Bandwidth::new(1_234, 567_891_234);
+        let ceil = |unit| bandwidth_ceil(bw, unit);
+        assert_eq!(ceil(BandwidthUnit::Bps), Some(bw));
+        assert_eq!(
+            ceil(BandwidthUnit::Kbps),
+            Some(Bandwidth::new(1_234, 567_892_000))
+        );
+        assert_eq!(
+            ceil(BandwidthUnit::Mbps),
+            Some(Bandwidth::new(1_234, 568_000_000))
+        );
+        assert_eq!(ceil(BandwidthUnit::Gbps), Some(Bandwidth::new(1_235, 0)));
+        assert_eq!(ceil(BandwidthUnit::Tbps), Some(Bandwidth::new(2_000, 0)));
+        assert_eq!(
+            bandwidth_ceil(Bandwidth::from_kbps(3), BandwidthUnit::Kbps),
+            Some(Bandwidth::from_kbps(3))
+        );
+        assert_eq!(
+            bandwidth_ceil(Bandwidth::ZERO, BandwidthUnit::Tbps),
+            Some(Bandwidth::ZERO)
+        );
+        assert_eq!(
+            bandwidth_ceil(Bandwidth::from_bps(1), BandwidthUnit::Tbps),
+            Some(Bandwidth::new(1_000, 0))
+        );
+        assert_eq!(
+            bandwidth_ceil(Bandwidth::MAX, BandwidthUnit::Bps),
+            Some(Bandwidth::MAX)
+        );
+        assert_eq!(bandwidth_ceil(Bandwidth::MAX, BandwidthUnit::Kbps), None);
+        assert_eq!(
+            bandwidth_ceil(Bandwidth::new(u64::MAX, 0), BandwidthUnit::Gbps),
+            Some(Bandwidth::new(u64::MAX, 0))
+        );
+        assert_eq!(
+            bandwidth_ceil(Bandwidth::new(u64::MAX, 0), BandwidthUnit::Tbps),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bandwidth_round() {
+        let bw = Bandwidth::new(1_234, 567_891_234);
+        let round = |unit| bandwidth_round(bw, unit);
+        assert_eq!(round(BandwidthUnit::Bps), bw);
+        assert_eq!(
+            round(BandwidthUnit::Kbps),
+            Bandwidth::new(1_234, 567_891_000)
+        );
+        assert_eq!(
+            round(BandwidthUnit::Mbps),
+            Bandwidth::new(1_234, 568_000_000)
+        );
+        assert_eq!(round(BandwidthUnit::Gbps), Bandwidth::new(1_235, 0));
+        assert_eq!(round(BandwidthUnit::Tbps), Bandwidth::new(1_000, 0));
+        // half up
+        assert_eq!(
+            bandwidth_round(Bandwidth::from_bps(1_500), BandwidthUnit::Kbps),
+            Bandwidth::from_kbps(2)
+        );
+        assert_eq!(
+            bandwidth_round(Bandwidth::from_bps(1_499), BandwidthUnit::Kbps),
+            Bandwidth::from_kbps(1)
+        );
+        assert_eq!(
+            bandwidth_round(Bandwidth::from_bps(499), BandwidthUnit::Kbps),
+            Bandwidth::ZERO
+        );
+        assert_eq!(
+            bandwidth_round(Bandwidth::new(500, 0), BandwidthUnit::Tbps),
+            Bandwidth::new(1_000, 0)
+        );
+        // rounding up would overflow, so it rounds down
+        assert_eq!(
+            bandwidth_round(Bandwidth::MAX, BandwidthUnit::Gbps),
+            Bandwidth::new(u64::MAX, 0)
+        );
+        assert_eq!(
+            bandwidth_round(Bandwidth::MAX, BandwidthUnit::Bps),
+            Bandwidth::MAX
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_format_bytes_human() {
+        assert_eq!(format_bytes_human(0), "0 B");
+        assert_eq!(format_bytes_human(999), "999 B");
+        assert_eq!(format_bytes_human(1_000), "1 kB");
+        assert_eq!(format_bytes_human(1_050), "1.1 kB");
+        assert_eq!(format_bytes_human(1_049), "1 kB");
+        assert_eq!(format_bytes_human(12_500_000), "12.5 MB");
+        assert_eq!(format_bytes_human(999_950), "1 MB");
+        assert_eq!(format_bytes_human(999_949), "999.9 kB");
+        assert_eq!(format_bytes_human(u64::MAX), "18.4 EB");
+    }
+}
@@ -0,0 +1,171 @@
+//! A newtype around `Bandwidth` that parses and displays in human form
+
+use crate::{format_bandwidth, parse_bandwidth, parse_bandwidth_partial, Error};
+use core::fmt;
+use core::iter::Sum;
+use core::ops::Deref;
+use core::str::FromStr;
+
+/// A wrapper for bandwidth that has `FromStr` implementation
+///
+/// This is useful if you want to use it somewhere where `FromStr` is
+/// expected.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::HumanBandwidth;
+///
+/// let x: HumanBandwidth = "1Gbps 500Mbps".parse().unwrap();
+/// assert_eq!(*x, Bandwidth::new(1, 500_000_000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HumanBandwidth(bandwidth::Bandwidth);
+
+impl HumanBandwidth {
+    /// Parses a bandwidth from the start of `s`, returning it together with
+    /// the unparsed remainder
+    ///
+    /// This calls [`parse_bandwidth_partial`]. The returned tail is the raw
+    /// unconsumed remainder of `s`, including any leading whitespace, so it
+    /// can be handed straight to another parser.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::HumanBandwidth;
+    ///
+    /// let (bw, tail) = HumanBandwidth::from_string_prefix("10Mbps for 5s").unwrap();
+    /// assert_eq!(*bw, Bandwidth::from_mbps(10));
+    /// assert_eq!(tail, " for 5s");
+    /// ```
+    pub fn from_string_prefix(s: &str) -> Result<(HumanBandwidth, &str), Error> {
+        parse_bandwidth_partial(s).map(|(bw, tail)| (HumanBandwidth(bw), tail))
+    }
+}
+
+impl AsRef<bandwidth::Bandwidth> for HumanBandwidth {
+    fn as_ref(&self) -> &bandwidth::Bandwidth {
+        &self.0
+    }
+}
+
+impl Deref for HumanBandwidth {
+    type Target = bandwidth::Bandwidth;
+
+    fn deref(&self) -> &bandwidth::Bandwidth {
+        &self.0
+    }
+}
+
+impl From<HumanBandwidth> for bandwidth::Bandwidth {
+    fn from(val: HumanBandwidth) -> bandwidth::Bandwidth {
+        val.0
+    }
+}
+
+impl From<bandwidth::Bandwidth> for HumanBandwidth {
+    fn from(val: bandwidth::Bandwidth) -> HumanBandwidth {
+        HumanBandwidth(val)
+    }
+}
+
+impl FromStr for HumanBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<HumanBandwidth, Error> {
+        parse_bandwidth(s).map(HumanBandwidth)
+    }
+}
+
+impl fmt::Display for HumanBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        format_bandwidth(self.0).fmt(f)
+    }
+}
+
+/// Sums the bandwidths, panicking on overflow like the `Sum` impl of
+/// `Bandwidth`
+///
+/// Use [`BandwidthSum`][crate::BandwidthSum] to detect overflow instead.
+impl Sum for HumanBandwidth {
+    fn sum<I: Iterator<Item = HumanBandwidth>>(iter: I) -> HumanBandwidth {
+        HumanBandwidth(iter.map(|bw| bw.0).sum())
+    }
+}
+
+impl<'a> Sum<&'a HumanBandwidth> for HumanBandwidth {
+    fn sum<I: Iterator<Item = &'a HumanBandwidth>>(iter: I) -> HumanBandwidth {
+        HumanBandwidth(iter.map(|bw| bw.0).sum())
+    }
+}
+
+impl Sum<bandwidth::Bandwidth> for HumanBandwidth {
+    fn sum<I: Iterator<Item = bandwidth::Bandwidth>>(iter: I) -> HumanBandwidth {
+        HumanBandwidth(iter.sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bandwidth::Bandwidth;
+    use std::time::Duration;
+
+    #[test]
+    fn test_from_str() {
+        let x: HumanBandwidth = "9Tbps 420Gbps".parse().unwrap();
+        assert_eq!(Bandwidth::from(x), Bandwidth::new(9420, 0));
+        assert!("1Gbit".parse::<HumanBandwidth>().is_err());
+        let x = HumanBandwidth::from(Bandwidth::from_mbps(32));
+        assert_eq!(x.to_string().parse::<HumanBandwidth>(), Ok(x));
+    }
+
+    #[test]
+    fn test_ord() {
+        let hb = |gbps, bps| HumanBandwidth::from(Bandwidth::new(gbps, bps));
+        assert!(hb(1, 5) < hb(1, 6));
+        assert!(hb(2, 0) > hb(1, 999_999_999));
+        assert!(hb(u64::MAX, 0) < HumanBandwidth::from(Bandwidth::MAX));
+        let mut vals = [hb(u64::MAX, 1), hb(0, 1), hb(u64::MAX, 0), hb(1, 0)];
+        vals.sort();
+        assert_eq!(vals, [hb(0, 1), hb(1, 0), hb(u64::MAX, 0), hb(u64::MAX, 1)]);
+    }
+
+    #[test]
+    fn test_sum() {
+        let bws = vec![HumanBandwidth::from(Bandwidth::from_gbps(1)); 10];
+        assert_eq!(
+            *bws.iter().sum::<HumanBandwidth>(),
+            Bandwidth::from_gbps(10)
+        );
+        assert_eq!(
+            *bws.into_iter().sum::<HumanBandwidth>(),
+            Bandwidth::from_gbps(10)
+        );
+        let total: HumanBandwidth = [Bandwidth::from_mbps(1), Bandwidth::from_kbps(1)]
+            .into_iter()
+            .sum();
+        assert_eq!(*total, Bandwidth::from_kbps(1001));
+    }
+
+    #[test]
+    fn test_from_string_prefix_humantime() {
+        let (bw, tail) = HumanBandwidth::from_string_prefix("100Mbps 5s").unwrap();
+        assert_eq!(*bw, Bandwidth::from_mbps(100));
+        assert_eq!(tail, " 5s");
+        assert_eq!(humantime::parse_duration(tail), Ok(Duration::from_secs(5)));
+
+        let (bw, tail) = HumanBandwidth::from_string_prefix("1Gbps 500Mbps2min 30s").unwrap();
+        assert_eq!(*bw, Bandwidth::new(1, 500_000_000));
+        assert_eq!(tail, "2min 30s");
+        assert_eq!(
+            humantime::parse_duration(tail),
+            Ok(Duration::from_secs(150))
+        );
+
+        assert!(HumanBandwidth::from_string_prefix("5s").is_err());
+    }
+}